@@ -1,5 +1,5 @@
 use eframe::NativeOptions;
-use egui_logger::{IntoCategories, TimeFormat, TimePrecision};
+use egui_logger::{Command, Cvar, InputChannel, IntoCategories, LogLevel, LogTab, TimeFormat, TimePrecision};
 use std::fmt;
 
 fn main() {
@@ -12,6 +12,32 @@ fn main() {
     logger.input_text_prefix = "User: ".to_string();
     logger.max_records_per_level = 5;
     logger.set_input_categories(vec![MyLogCategory::Input, MyLogCategory::Dialogue]);
+    logger.set_input_channels(vec![
+        InputChannel::new("Say", vec![MyLogCategory::Input, MyLogCategory::Dialogue]),
+        InputChannel::new("Debug", vec![MyLogCategory::Input, MyLogCategory::Combat]),
+    ]);
+    logger.input_levels = vec![LogLevel::Info, LogLevel::Warn];
+    logger.set_tabs(vec![
+        LogTab::new("All"),
+        LogTab::new("Combat").categories(vec![MyLogCategory::Combat]),
+        LogTab::new("Problems").min_level(LogLevel::Warn).input_channel("Debug"),
+    ]);
+    logger.register_command(
+        Command::new("roll", "Roll a die", |logger, args| {
+            let sides: u32 = args.parse_optional(0)?.unwrap_or(6);
+            if sides == 0 {
+                return Err("A die needs at least one side".to_string());
+            }
+            let roll = chrono::Local::now().timestamp_subsec_nanos() % sides + 1;
+            logger.log_info(MyLogCategory::Dialogue, format!("Rolled {roll} (1-{sides})"));
+            Ok(())
+        })
+        .optional_arg("sides")
+        .completer(|_| ["4", "6", "8", "12", "20"].map(String::from).to_vec()),
+    );
+
+    logger.register_cvar(Cvar::new("net_latency_sim", 0, "Simulated network latency in ms").range(0.0, 1000.0));
+    logger.register_cvar(Cvar::new("show_fps", false, "Show the frame rate"));
 
     let app = MyApp::new(logger);
 
@@ -57,6 +83,9 @@ impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::widgets::global_theme_preference_buttons(ui);
+            if self.logger.cvar_value("show_fps").and_then(|v| v.as_bool()) == Some(true) {
+                ui.label(format!("{:.0} fps", 1.0 / ctx.input(|i| i.stable_dt).max(f32::EPSILON)));
+            }
 
             if ui.button("This produces Debug Info").clicked() {
                 self.logger.log_debug(MyLogCategory::Network, "Connecting...")
@@ -79,12 +108,11 @@ impl eframe::App for MyApp {
             ui.separator();
             ui.label("When in the text input area, CTRL+F goes to search. You can tab between interactive things.");
             ui.label("Pressing Enter gets back to the input area, except if in the search input area, where it just exits that.");
+            ui.label("Type /help in the input area to list commands, such as /roll and /set show_fps 1. Tab completes commands and arguments.");
             ui.label("In a real application, how to get users into the text area is up to you. It is exposed through setting `logger.should_focus_input` to true. This demo handles it by watching for for presses of the Enter key.");
             // Only handle Enter if nothing has focus
-            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                if ctx.memory(|mem| mem.focused()).is_none() {
-                    self.logger.should_focus_input = true;
-                }
+            if ui.input(|i| i.key_pressed(egui::Key::Enter)) && ctx.memory(|mem| mem.focused()).is_none() {
+                self.logger.should_focus_input = true;
             }
         });
         egui::TopBottomPanel::bottom("chat area")
@@ -93,5 +121,11 @@ impl eframe::App for MyApp {
             .show(ctx, |ui| {
                 self.logger.show(ui);
             });
+        for input in self.logger.take_submitted_inputs() {
+            // A real game might send this to a server. Here the logger already echoes it.
+            if input.text.eq_ignore_ascii_case("ping") {
+                self.logger.log_info(MyLogCategory::Network, "pong");
+            }
+        }
     }
 }
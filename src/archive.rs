@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::record::LogRecord;
+
+/// Size of the chunks read when scanning backwards through the archive file.
+const READ_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// An append-only file that records evicted from an [`crate::EguiLogger`] are written to, so
+/// they can be paged back into the view later.
+///
+/// Records are stored one JSON object per line, in the order they were evicted.
+/// Pages are read backwards from the end of the file, so each "Load older" request returns the
+/// records that were evicted before the ones already loaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogArchive {
+    path: PathBuf,
+    /// Number of records read from the archive per [`crate::EguiLogger::load_older_archived`] call.
+    pub page_size: usize,
+
+    // Opened lazily on the first write.
+    #[serde(skip)]
+    file: Option<Arc<File>>,
+    // Byte offset in the file of the oldest record that has been loaded.
+    // None if nothing has been loaded yet.
+    #[serde(skip)]
+    loaded_from: Option<u64>,
+    // Records that have been read back from the archive, and are shown alongside the in-memory records.
+    #[serde(skip)]
+    loaded: Vec<LogRecord>,
+    // Length of the archive file, kept up to date by `append` so `has_older` doesn't have to ask
+    // the file system every frame.
+    #[serde(skip)]
+    file_len: u64,
+    // Ids given to the loaded records. See `load_older`.
+    #[serde(skip)]
+    loaded_ids: HashSet<u64>,
+    // The most recent error from reading or writing the archive.
+    #[serde(skip)]
+    last_error: Option<String>,
+}
+
+impl LogArchive {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        let mut archive = Self {
+            path: path.into(),
+            page_size: 200,
+            file: None,
+            file_len: 0,
+            loaded_from: None,
+            loaded: Vec::new(),
+            loaded_ids: HashSet::new(),
+            last_error: None,
+        };
+        archive.refresh_file_len();
+        archive
+    }
+
+    /// Reads the length of the archive file, which may already hold records from earlier sessions.
+    pub(crate) fn refresh_file_len(&mut self) {
+        self.file_len = std::fs::metadata(&self.path).map_or(0, |m| m.len());
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The most recent error that occurred while reading or writing the archive, if any.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Records that have been read back from the archive.
+    pub(crate) fn loaded(&self) -> &[LogRecord] {
+        &self.loaded
+    }
+
+    pub(crate) fn loaded_mut(&mut self) -> &mut [LogRecord] {
+        &mut self.loaded
+    }
+
+    /// Forget the records that were read back. The archive file itself is left untouched.
+    pub(crate) fn clear_loaded(&mut self) {
+        self.loaded.clear();
+        self.loaded_ids.clear();
+        self.loaded_from = None;
+    }
+
+    /// Whether there are archived records older than the ones already loaded.
+    pub fn has_older(&self) -> bool {
+        match self.loaded_from {
+            Some(offset) => offset > 0,
+            None => self.file_len > 0,
+        }
+    }
+
+    /// Appends a record to the end of the archive file.
+    /// Returns false if the record couldn't be written, in which case the error is kept.
+    pub(crate) fn append(&mut self, record: &LogRecord) -> bool {
+        if let Err(e) = self.try_append(record) {
+            self.last_error = Some(e.to_string());
+            return false;
+        }
+        // Once the user has started browsing the archive, newly evicted records would otherwise
+        // disappear from the view, leaving a gap between the loaded pages and the in-memory records.
+        if self.loaded_from.is_some() {
+            self.loaded.push(record.clone());
+        }
+        true
+    }
+
+    fn try_append(&mut self, record: &LogRecord) -> io::Result<()> {
+        if self.file.is_none() {
+            let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+            self.file = Some(Arc::new(file));
+        }
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        self.file.as_deref().unwrap().write_all(&line)?;
+        self.file_len += line.len() as u64;
+        Ok(())
+    }
+
+    /// Whether the record with this id was loaded back from the archive.
+    pub(crate) fn is_loaded(&self, id: u64) -> bool {
+        self.loaded_ids.contains(&id)
+    }
+
+    /// Loads the next page of older records. They're given new ids, starting at `next_id`, since
+    /// the ids they were stored with may be in use by records of a later session sharing the file.
+    /// Returns the number of records that were loaded.
+    pub(crate) fn load_older(&mut self, next_id: &mut u64) -> io::Result<usize> {
+        let result = self.try_load_older(next_id);
+        if let Err(e) = &result {
+            self.last_error = Some(e.to_string());
+        }
+        result
+    }
+
+    fn try_load_older(&mut self, next_id: &mut u64) -> io::Result<usize> {
+        let mut file = File::open(&self.path)?;
+        let end = match self.loaded_from {
+            Some(offset) => offset,
+            None => file.metadata()?.len(),
+        };
+        let (start, mut records) = read_lines_before(&mut file, end, self.page_size)?;
+        let count = records.len();
+        for record in &mut records {
+            record.id = *next_id;
+            *next_id += 1;
+            self.loaded_ids.insert(record.id);
+        }
+        self.loaded_from = Some(start);
+        self.loaded.extend(records);
+        Ok(count)
+    }
+}
+
+/// Reads up to `max_lines` complete lines that end at or before the byte offset `end`, which
+/// must be at the start of a line. Returns the offset of the first line that was read, along
+/// with the parsed records. Lines that fail to parse are skipped.
+fn read_lines_before(file: &mut File, end: u64, max_lines: usize) -> io::Result<(u64, Vec<LogRecord>)> {
+    let mut start = end;
+    let mut buf: Vec<u8> = Vec::new();
+    // Every line ends with a newline, so `max_lines` complete lines need one more newline in
+    // front of them, unless they start at the beginning of the file.
+    while start > 0 && buf.iter().filter(|b| **b == b'\n').count() <= max_lines {
+        let chunk_start = start.saturating_sub(READ_CHUNK_SIZE);
+        let mut chunk = vec![0; (start - chunk_start) as usize];
+        file.seek(SeekFrom::Start(chunk_start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+        start = chunk_start;
+    }
+
+    let newlines: Vec<usize> = buf
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| **b == b'\n')
+        .map(|(i, _)| i)
+        .collect();
+    let cut = if newlines.len() > max_lines {
+        newlines[newlines.len() - max_lines - 1] + 1
+    } else {
+        0
+    };
+
+    let records = buf[cut..]
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| serde_json::from_slice(line).ok())
+        .collect();
+    Ok((start + cut as u64, records))
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Local, TimeDelta};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The source of timestamps for new records. See [`crate::EguiLogger::set_clock`].
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Local>;
+}
+
+/// The system clock. This is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WallClock;
+
+impl Clock for WallClock {
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+/// A clock that only moves when told to, for deterministic tests or replays.
+/// Clones share the same time, so a clone can be kept to move the clock after giving it to
+/// the logger.
+///
+/// ```
+/// # use egui_logger::{EguiLogger, ManualClock};
+/// let clock = ManualClock::new(chrono::Local::now());
+/// let mut logger = EguiLogger::new();
+/// logger.set_clock(clock.clone());
+/// logger.log_info("Test", "first");
+/// clock.advance(chrono::TimeDelta::seconds(5));
+/// logger.log_info("Test", "second");
+/// let records = logger.filtered_records();
+/// assert_eq!(records[1].timestamp - records[0].timestamp, chrono::TimeDelta::seconds(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    time: Arc<Mutex<DateTime<Local>>>,
+}
+
+impl ManualClock {
+    pub fn new(start: DateTime<Local>) -> Self {
+        Self {
+            time: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    pub fn set(&self, time: DateTime<Local>) {
+        *self.time.lock().unwrap() = time;
+    }
+
+    pub fn advance(&self, delta: TimeDelta) {
+        *self.time.lock().unwrap() += delta;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Local> {
+        *self.time.lock().unwrap()
+    }
+}
+
+/// Provides the app's own timestamp for new records, e.g. a frame number or in-game time.
+/// See [`crate::EguiLogger::set_app_time`].
+pub type AppTimeSource = Arc<dyn Fn() -> String + Send + Sync>;
+
+/// The clock and app time source of a logger. These can't be serialized, so they need to be set
+/// again after loading the logger.
+#[derive(Clone)]
+pub(crate) struct TimeSources {
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) app_time: Option<AppTimeSource>,
+}
+
+impl Default for TimeSources {
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(WallClock),
+            app_time: None,
+        }
+    }
+}
+
+impl fmt::Debug for TimeSources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeSources")
+            .field("has_app_time", &self.app_time.is_some())
+            .finish_non_exhaustive()
+    }
+}
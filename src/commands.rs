@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::logger::EguiLogger;
+
+/// The function run when a command is submitted through the input area.
+/// Returning an error logs it as an Error record.
+pub type CommandHandler = Arc<dyn Fn(&mut EguiLogger, &CommandArgs) -> Result<(), String> + Send + Sync>;
+
+/// Provides the candidates for tab completion of the argument at the given index, e.g. player
+/// names or item ids. Candidates are filtered by what the user has typed so far.
+pub type ArgCompleter = Arc<dyn Fn(usize) -> Vec<String> + Send + Sync>;
+
+#[derive(Debug, Clone)]
+struct CommandArg {
+    name: String,
+    required: bool,
+}
+
+/// A command that can be run from the input area by typing `/name arg1 arg2 ...`.
+///
+/// ```
+/// # use egui_logger::{Command, EguiLogger};
+/// let mut logger = EguiLogger::new();
+/// logger.register_command(
+///     Command::new("teleport", "Move the player", |logger, args| {
+///         let x: f32 = args.parse(0)?;
+///         let y: f32 = args.parse(1)?;
+///         logger.log_info("Input", format!("Teleported to {x}, {y}"));
+///         Ok(())
+///     })
+///     .arg("x")
+///     .arg("y"),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Command {
+    name: String,
+    description: String,
+    args: Vec<CommandArg>,
+    // Whether any number of arguments may follow the declared ones.
+    rest: Option<String>,
+    handler: CommandHandler,
+    completer: Option<ArgCompleter>,
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Command")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("args", &self.args)
+            .field("rest", &self.rest)
+            .field("has_completer", &self.completer.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Command {
+    /// Create a command. `name` is what users type after the `/`.
+    pub fn new<F>(name: impl Into<String>, description: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(&mut EguiLogger, &CommandArgs) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            args: Vec::new(),
+            rest: None,
+            handler: Arc::new(handler),
+            completer: None,
+        }
+    }
+
+    /// Add a required argument. Arguments are positional, in the order they are added.
+    pub fn arg(mut self, name: impl Into<String>) -> Self {
+        self.args.push(CommandArg {
+            name: name.into(),
+            required: true,
+        });
+        self
+    }
+
+    /// Add an optional argument. Optional arguments must come after all required ones.
+    pub fn optional_arg(mut self, name: impl Into<String>) -> Self {
+        self.args.push(CommandArg {
+            name: name.into(),
+            required: false,
+        });
+        self
+    }
+
+    /// Accept any number of additional arguments after the declared ones, e.g. a chat message.
+    /// Use [`CommandArgs::rest`] to get them.
+    pub fn rest_arg(mut self, name: impl Into<String>) -> Self {
+        self.rest = Some(name.into());
+        self
+    }
+
+    /// Provide tab completion candidates for arguments. The function is given the index of the
+    /// argument being completed.
+    pub fn completer<F>(mut self, completer: F) -> Self
+    where
+        F: Fn(usize) -> Vec<String> + Send + Sync + 'static,
+    {
+        self.completer = Some(Arc::new(completer));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Usage text, e.g. "/teleport <x> <y> [z]".
+    pub fn usage(&self) -> String {
+        let mut usage = format!("/{}", self.name);
+        for arg in &self.args {
+            if arg.required {
+                usage.push_str(&format!(" <{}>", arg.name));
+            } else {
+                usage.push_str(&format!(" [{}]", arg.name));
+            }
+        }
+        if let Some(rest) = &self.rest {
+            usage.push_str(&format!(" [{}...]", rest));
+        }
+        usage
+    }
+
+    /// Checks the number of arguments, returning a user facing error if it's wrong.
+    fn validate(&self, args: &[String]) -> Result<(), String> {
+        if let Some(missing) = self.args.iter().filter(|a| a.required).nth(args.len()) {
+            return Err(format!("Missing argument <{}>. Usage: {}", missing.name, self.usage()));
+        }
+        if self.rest.is_none() && args.len() > self.args.len() {
+            return Err(format!("Too many arguments. Usage: {}", self.usage()));
+        }
+        Ok(())
+    }
+}
+
+/// The arguments passed to a command handler.
+#[derive(Debug, Clone)]
+pub struct CommandArgs {
+    command: String,
+    names: Vec<String>,
+    args: Vec<String>,
+}
+
+impl CommandArgs {
+    /// The name of the command being run, without the `/`.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// The argument at `index`, if provided.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.args.get(index).map(|s| s.as_str())
+    }
+
+    /// Parse the argument at `index`, with a user facing error if it's missing or invalid.
+    pub fn parse<T: FromStr>(&self, index: usize) -> Result<T, String> {
+        let name = self.names.get(index).map_or("argument", |n| n.as_str());
+        let value = self.get(index).ok_or_else(|| format!("Missing argument <{}>", name))?;
+        value
+            .parse()
+            .map_err(|_| format!("Invalid value '{}' for <{}>", value, name))
+    }
+
+    /// Parse the argument at `index` if it was provided.
+    pub fn parse_optional<T: FromStr>(&self, index: usize) -> Result<Option<T>, String> {
+        if self.get(index).is_none() {
+            return Ok(None);
+        }
+        self.parse(index).map(Some)
+    }
+
+    /// All arguments from `index` onwards, joined by spaces.
+    pub fn rest(&self, index: usize) -> String {
+        self.args.get(index..).unwrap_or_default().join(" ")
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.args.iter().map(|s| s.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CommandRegistry {
+    commands: BTreeMap<String, Command>,
+}
+
+impl CommandRegistry {
+    pub(crate) fn register(&mut self, command: Command) {
+        self.commands.insert(command.name.clone(), command);
+    }
+
+    pub(crate) fn unregister(&mut self, name: &str) -> Option<Command> {
+        self.commands.remove(name)
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.get(name)
+    }
+
+    /// Registered commands, sorted by name.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.commands.values()
+    }
+
+    /// Prepares a command to be run. Returns the handler and its arguments, or a user facing
+    /// error if the command doesn't exist or the arguments are wrong.
+    pub(crate) fn prepare(&self, name: &str, args: Vec<String>) -> Result<(CommandHandler, CommandArgs), String> {
+        let command = self
+            .get(name)
+            .ok_or_else(|| format!("Unknown command '/{}'. Type /help for a list of commands.", name))?;
+        command.validate(&args)?;
+        let mut names: Vec<String> = command.args.iter().map(|a| a.name.clone()).collect();
+        if let Some(rest) = &command.rest {
+            names.resize(args.len().max(names.len()), rest.clone());
+        }
+        let args = CommandArgs {
+            command: name.to_string(),
+            names,
+            args,
+        };
+        Ok((command.handler.clone(), args))
+    }
+}
+
+/// Tab completion state for the input area.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Completion {
+    /// Byte offset in the input text where the completed token starts.
+    pub(crate) token_start: usize,
+    pub(crate) candidates: Vec<String>,
+    /// Index of the candidate that was last applied to the input text.
+    pub(crate) selected: Option<usize>,
+}
+
+impl CommandRegistry {
+    /// Computes completion candidates for the last token of `input`, which should start with `/`.
+    /// `builtins` are the names of built-in commands, which are used unless a registered command
+    /// has the same name. `builtin_args` provides their argument candidates.
+    /// Returns `None` if there is nothing to complete.
+    pub(crate) fn completions(
+        &self,
+        input: &str,
+        builtins: &[&str],
+        builtin_args: impl Fn(&str, usize) -> Vec<String>,
+    ) -> Option<Completion> {
+        let command_line = input.strip_prefix('/')?;
+        let token_start = input
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(1, |(i, c)| i + c.len_utf8());
+        let partial = input[token_start..].to_lowercase();
+        let preceding: Vec<&str> = command_line[..token_start - 1].split_whitespace().collect();
+
+        let mut candidates: Vec<String> = match preceding.split_first() {
+            // Completing the command name itself.
+            None => self
+                .commands
+                .keys()
+                .map(|s| s.as_str())
+                .chain(builtins.iter().copied())
+                .map(|name| name.to_string())
+                .collect(),
+            Some((name, args)) => match self.get(name) {
+                Some(command) => command.completer.as_ref().map_or(Vec::new(), |c| c(args.len())),
+                None => builtin_args(name, args.len()),
+            },
+        };
+        candidates.retain(|c| c.to_lowercase().starts_with(&partial) && c.to_lowercase() != partial);
+        candidates.sort();
+        candidates.dedup();
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(Completion {
+            token_start,
+            candidates,
+            selected: None,
+        })
+    }
+}
+
+impl Completion {
+    /// Moves the selection forwards or backwards, wrapping around, and returns the input text
+    /// with the selected candidate applied.
+    pub(crate) fn cycle(&mut self, input: &str, forward: bool) -> String {
+        let len = self.candidates.len();
+        let selected = match (self.selected, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+        };
+        self.select(input, selected)
+    }
+
+    /// Selects the candidate at `index`, and returns the input text with it applied.
+    pub(crate) fn select(&mut self, input: &str, index: usize) -> String {
+        self.selected = Some(index);
+        let candidate = &self.candidates[index];
+        let candidate = if candidate.contains(char::is_whitespace) {
+            format!("\"{}\"", candidate)
+        } else {
+            candidate.clone()
+        };
+        format!("{}{}", &input[..self.token_start], candidate)
+    }
+}
+
+/// Splits command input into whitespace separated tokens. Double quotes group words into a
+/// single token, e.g. `/say "hello world"`.
+pub(crate) fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        tokens.push(current);
+    }
+    tokens
+}
@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The value of a console variable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CvarValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl CvarValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CvarValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            CvarValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            CvarValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CvarValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            CvarValue::Bool(_) => "bool",
+            CvarValue::Int(_) => "int",
+            CvarValue::Float(_) => "float",
+            CvarValue::String(_) => "string",
+        }
+    }
+
+    fn same_type(&self, other: &CvarValue) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            CvarValue::Int(i) => Some(*i as f64),
+            CvarValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Parses `text` as a value of the same type as `self`.
+    fn parse_same_type(&self, text: &str) -> Result<CvarValue, String> {
+        let invalid = || format!("'{}' is not a valid {}", text, self.type_name());
+        match self {
+            CvarValue::Bool(_) => match text.to_lowercase().as_str() {
+                "1" | "true" | "on" | "yes" => Ok(CvarValue::Bool(true)),
+                "0" | "false" | "off" | "no" => Ok(CvarValue::Bool(false)),
+                _ => Err(invalid()),
+            },
+            CvarValue::Int(_) => text.parse().map(CvarValue::Int).map_err(|_| invalid()),
+            CvarValue::Float(_) => text.parse().map(CvarValue::Float).map_err(|_| invalid()),
+            CvarValue::String(_) => Ok(CvarValue::String(text.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for CvarValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CvarValue::Bool(b) => write!(f, "{}", if *b { 1 } else { 0 }),
+            CvarValue::Int(i) => write!(f, "{}", i),
+            CvarValue::Float(v) => write!(f, "{}", v),
+            CvarValue::String(s) => write!(f, "\"{}\"", s),
+        }
+    }
+}
+
+impl From<bool> for CvarValue {
+    fn from(value: bool) -> Self {
+        CvarValue::Bool(value)
+    }
+}
+
+impl From<i32> for CvarValue {
+    fn from(value: i32) -> Self {
+        CvarValue::Int(value as i64)
+    }
+}
+
+impl From<i64> for CvarValue {
+    fn from(value: i64) -> Self {
+        CvarValue::Int(value)
+    }
+}
+
+impl From<f32> for CvarValue {
+    fn from(value: f32) -> Self {
+        CvarValue::Float(value as f64)
+    }
+}
+
+impl From<f64> for CvarValue {
+    fn from(value: f64) -> Self {
+        CvarValue::Float(value)
+    }
+}
+
+impl From<&str> for CvarValue {
+    fn from(value: &str) -> Self {
+        CvarValue::String(value.to_string())
+    }
+}
+
+impl From<String> for CvarValue {
+    fn from(value: String) -> Self {
+        CvarValue::String(value)
+    }
+}
+
+/// A console variable: a named, typed value that can be inspected and changed from the input
+/// area with `/get`, `/set`, `/reset` and `/list`.
+///
+/// ```
+/// # use egui_logger::{Cvar, EguiLogger};
+/// let mut logger = EguiLogger::new();
+/// logger.register_cvar(Cvar::new("net_latency_sim", 0, "Simulated latency in ms").range(0.0, 1000.0));
+/// logger.submit_input("/set net_latency_sim 120");
+/// assert_eq!(logger.cvar_value("net_latency_sim").unwrap().as_int(), Some(120));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cvar {
+    name: String,
+    description: String,
+    value: CvarValue,
+    default: CvarValue,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Cvar {
+    pub fn new(name: impl Into<String>, default: impl Into<CvarValue>, description: impl Into<String>) -> Self {
+        let default = default.into();
+        Self {
+            name: name.into(),
+            description: description.into(),
+            value: default.clone(),
+            default,
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Limit an int or float cvar to the inclusive range `min..=max`.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn value(&self) -> &CvarValue {
+        &self.value
+    }
+
+    pub fn default_value(&self) -> &CvarValue {
+        &self.default
+    }
+
+    /// Checks that `value` has the right type and is within range, with a user facing error if not.
+    pub(crate) fn validate(&self, value: &CvarValue) -> Result<(), String> {
+        if !value.same_type(&self.default) {
+            return Err(format!(
+                "{} is a {}, not a {}",
+                self.name,
+                self.default.type_name(),
+                value.type_name()
+            ));
+        }
+        if let Some(n) = value.as_number() {
+            // NaN would pass the range checks below.
+            if !n.is_finite() {
+                return Err(format!("{} must be a finite number", self.name));
+            }
+            let below = self.min.is_some_and(|min| n < min);
+            let above = self.max.is_some_and(|max| n > max);
+            if below || above {
+                return Err(format!(
+                    "{} must be in the range {}",
+                    self.name,
+                    self.range_text().unwrap()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parse(&self, text: &str) -> Result<CvarValue, String> {
+        let value = self.default.parse_same_type(text)?;
+        self.validate(&value)?;
+        Ok(value)
+    }
+
+    /// Sets the value, returning the previous one. The value must already be validated.
+    pub(crate) fn replace(&mut self, value: CvarValue) -> CvarValue {
+        std::mem::replace(&mut self.value, value)
+    }
+
+    fn range_text(&self) -> Option<String> {
+        match (self.min, self.max) {
+            (None, None) => None,
+            (min, max) => Some(format!(
+                "[{}, {}]",
+                min.map_or("-inf".to_string(), |m| m.to_string()),
+                max.map_or("inf".to_string(), |m| m.to_string())
+            )),
+        }
+    }
+
+    /// One line summary, e.g. `net_latency_sim = 120 (int, default 0, range [0, 1000]) - Simulated latency`.
+    pub(crate) fn summary(&self) -> String {
+        let range = self.range_text().map(|r| format!(", range {}", r)).unwrap_or_default();
+        format!(
+            "{} = {} ({}, default {}{}) - {}",
+            self.name,
+            self.value,
+            self.default.type_name(),
+            self.default,
+            range,
+            self.description
+        )
+    }
+}
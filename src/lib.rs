@@ -1,16 +1,32 @@
 #![doc = include_str!("../README.md")]
 
+mod archive;
 mod categories;
+mod clock;
+mod commands;
+mod cvars;
 mod logger;
+mod rate_limit;
 mod record;
+mod tabs;
 mod tests;
+mod timeline;
 mod types;
 mod ui;
 mod utils;
 
 // Re-export public API
+pub use archive::LogArchive;
 pub use categories::IntoCategories;
+pub use clock::{AppTimeSource, Clock, ManualClock, WallClock};
+pub use commands::{ArgCompleter, Command, CommandArgs, CommandHandler};
+pub use cvars::{Cvar, CvarValue};
 pub use logger::EguiLogger;
-pub use record::LogRecord;
-pub use types::{LogLevel, TimeFormat, TimePrecision};
+pub use rate_limit::{RateLimit, RateLimitStats};
+pub use record::{EvictedRecords, LogRecord};
+pub use tabs::LogTab;
+pub use types::{
+    AppTimeDisplay, DetailsPosition, DisplayZone, InputChannel, LogColumn, LogLayout, LogLevel, RepeatCollapse,
+    SubmittedInput, TableColumn, TimeFormat, TimePrecision, TimeRange,
+};
 pub use utils::{deserialize_color32, serialize_color32};
@@ -1,13 +1,32 @@
-use chrono::Local;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, Local, TimeDelta};
 use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 
+use crate::archive::LogArchive;
 use crate::categories::IntoCategories;
-use crate::record::LogRecord;
-use crate::types::{LogLevel, TimeFormat, TimePrecision};
+use crate::clock::{Clock, TimeSources};
+use crate::commands::{Command, CommandRegistry, Completion, tokenize};
+use crate::cvars::{Cvar, CvarValue};
+use crate::rate_limit::{RateLimit, RateLimitKey, RateLimitStats, RateLimiter};
+use crate::record::{EvictedRecords, LogRecord};
+use crate::tabs::{LogTab, search_matches};
+use crate::timeline::Timeline;
+use crate::types::{
+    AppTimeDisplay, DetailsPosition, DisplayZone, InputChannel, LogColumn, LogLayout, LogLevel, RepeatCollapse,
+    SubmittedInput, TableColumn, TimeFormat, TimePrecision, TimeRange,
+};
+
+/// Maximum number of submitted inputs kept for [`EguiLogger::take_submitted_inputs`]. Older ones
+/// are dropped, so apps that don't use them don't accumulate them forever.
+const MAX_SUBMITTED_INPUTS: usize = 1000;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+// Fields missing from a saved logger, e.g. one saved by an older version, get their default value.
+// The derived (de)serialization is wrapped by the impls below, to repair loggers after loading.
+#[serde(default, remote = "Self")]
 pub struct EguiLogger {
     // TODO: Switch to a BinaryHeap. This will be way more efficient when iterating over all records
     //   since it will have them sorted by timestamp for free. It should also be able to do a fixed max size? Try using itertools?
@@ -23,6 +42,9 @@ pub struct EguiLogger {
     // past.
     category_counts: HashMap<String, u32>,
 
+    /// Minimum log level to store. Records below this level are dropped when they're logged, before
+    /// their message is formatted. Unlike `min_display_level`, these records can't be shown later.
+    pub min_capture_level: LogLevel,
     /// Minimum log level to display (e.g. Info will display Info,Warn,Error but not Debug)
     pub min_display_level: LogLevel,
     /// Categories that should be hidden.
@@ -36,6 +58,20 @@ pub struct EguiLogger {
     /// How to format timestamps
     pub time_format: TimeFormat,
     pub time_precision: TimePrecision,
+    /// Time zone for `TimeFormat::LocalTime` and `TimeFormat::Custom`.
+    pub time_zone: DisplayZone,
+    /// strftime pattern used by `TimeFormat::Custom`, e.g. "%Y-%m-%d %H:%M:%S". Invalid patterns
+    /// fall back to the local time.
+    pub custom_time_format: String,
+    // When the logger was created or its clock was set, for `TimeFormat::SinceStart`. Not saved,
+    // since it marks the start of a session.
+    #[serde(skip, default = "Local::now")]
+    start_time: DateTime<Local>,
+    /// How the app time of records is shown, if they have one.
+    pub app_time_display: AppTimeDisplay,
+    // Clock for new records, and the app's own time. See `set_clock` and `set_app_time`.
+    #[serde(skip)]
+    time_sources: TimeSources,
 
     /// Whether to show a categories in the text.
     pub show_categories: bool,
@@ -59,10 +95,41 @@ pub struct EguiLogger {
     /// logs. Note that in this example, the display might look odd at an older timestamp.
     /// There might appear to be many error logs and no debug logs, because the older debug logs
     /// have been removed. This is a natural consequence of this approach and is probably ok.
-    /// TODO: Call this out in documentation, examples, and tests. Or find an alternative that's
-    ///   more obvious to the user.
+    /// To make it obvious to the user, a marker row is shown when records have been dropped.
+    /// See `show_evicted_marker`.
     /// TODO: Consider removing records after a time delay (a few hours? one game session?).
     pub max_records_per_level: usize,
+    /// Approximate maximum number of bytes used by all stored records combined. When exceeded,
+    /// records are evicted starting with the oldest records of the lowest log level, so Debug
+    /// logs are dropped before Info, Info before Warn, and so on.
+    /// `None` disables the budget, leaving only `max_records_per_level` as a limit.
+    pub max_total_bytes: Option<usize>,
+    // Approximate number of bytes used by all stored records. Kept up to date as new records are
+    // added and old are removed.
+    memory_usage: usize,
+    // Records removed due to `max_records_per_level` or `max_total_bytes`, per level.
+    evicted: HashMap<LogLevel, EvictedRecords>,
+    // The evicted records that weren't written to an archive, so they're gone for good.
+    dropped: HashMap<LogLevel, EvictedRecords>,
+    /// Whether to show a marker at the top of the log (and in copied text) stating how many
+    /// older records were dropped. Records written to the archive aren't dropped, since they can
+    /// be loaded back.
+    pub show_evicted_marker: bool,
+    // If set, evicted records are appended to this archive rather than being lost.
+    archive: Option<LogArchive>,
+    /// Whether repeated identical records are merged into a single record with a repeat count.
+    /// A merged record only counts once towards the category counts and record limits.
+    pub collapse_repeats: RepeatCollapse,
+    // Level of the most recently logged record. Used to find it for `RepeatCollapse::Consecutive`.
+    #[serde(skip)]
+    last_logged_level: Option<LogLevel>,
+    // Rate limits for records with a given category. See `set_category_rate_limit`.
+    category_rate_limits: HashMap<String, RateLimit>,
+    // Rate limits for records of a given level. See `set_level_rate_limit`.
+    level_rate_limits: HashMap<LogLevel, RateLimit>,
+    // State of each active rate limit.
+    #[serde(skip)]
+    rate_limiters: HashMap<RateLimitKey, RateLimiter>,
     /// Current search term for filtering.
     pub search_term: String,
     // Storing this regex isn't important since it's generated from the search_term.
@@ -73,6 +140,67 @@ pub struct EguiLogger {
     pub search_with_regex: bool,
     /// Whether search should be case sensitive. This also applies to regex search.
     pub search_with_case_sensitive: bool,
+    /// Whether to only show bookmarked records. See [`Self::bookmark_record`].
+    pub show_only_bookmarked: bool,
+    /// Only show records logged within this range. `None` shows records from any time.
+    pub time_range: Option<TimeRange>,
+    /// Whether the log area keeps scrolling to show new records while scrolled to the bottom.
+    /// When disabled, the view stays where it is and new records are counted as unread.
+    pub follow_newest: bool,
+    // Id for the next stored record. Saved so ids stay unique across sessions.
+    next_record_id: u64,
+    // Whether the log area was scrolled to the bottom in the last frame.
+    #[serde(skip)]
+    scrolled_to_bottom: bool,
+    // Id of the oldest record stored since the user scrolled away from the bottom, or stopped
+    // following new records.
+    #[serde(skip)]
+    first_unread_id: Option<u64>,
+    // A trigger for the UI to scroll to the bottom of the log area in the next frame.
+    #[serde(skip)]
+    scroll_to_newest: bool,
+    // Records shown while the view is paused, sorted by timestamp. `None` when not paused.
+    #[serde(skip)]
+    paused_records: Option<Vec<LogRecord>>,
+    // Id of the first record stored after the view was paused.
+    #[serde(skip)]
+    paused_at_id: u64,
+    // Ids of the selected records. Ids don't change, so the selection survives new records.
+    #[serde(skip)]
+    selected_ids: BTreeSet<u64>,
+    // Record that Shift+click and Shift+Up/Down extend the selection from.
+    #[serde(skip)]
+    selection_anchor: Option<u64>,
+    // Most recently selected record, which Up/Down move from.
+    #[serde(skip)]
+    selection_cursor: Option<u64>,
+    // A trigger for the UI to scroll to `selection_cursor` in the next frame.
+    #[serde(skip)]
+    scroll_to_selection: bool,
+    /// Whether records are shown as lines of text or as a table.
+    pub layout: LogLayout,
+    /// Columns of the table layout, in display order. Widths are adjusted by dragging the edges of
+    /// the column headers, and columns can be hidden from the header's context menu.
+    pub table_columns: Vec<TableColumn>,
+    /// Column the table layout is sorted by. `None` shows records in chronological order.
+    pub sort_column: Option<LogColumn>,
+    pub sort_descending: bool,
+    /// Whether the text of the records can be selected by dragging over it, in which case Ctrl+C
+    /// copies the selected text rather than the selected records. Clicking still selects records.
+    pub select_text: bool,
+    /// Whether to show a pane with the details of the selected record.
+    pub show_details: bool,
+    pub details_position: DetailsPosition,
+    /// Width of the detail pane when shown on the right, or height when shown at the bottom.
+    /// Can be resized by dragging its edge.
+    pub details_size: f32,
+    /// Whether to show a strip above the log area with the number of records of each level over
+    /// time. Dragging on it sets `time_range`, and clicking it scrolls to that moment.
+    pub show_timeline: bool,
+    // Filtered views shown as tabs above the log area. See `set_tabs`.
+    tabs: Vec<LogTab>,
+    // Index into `tabs` of the tab being shown. Its filters are the ones above.
+    active_tab: usize,
 
     // Fields related to the text box and user input.
 
@@ -82,6 +210,9 @@ pub struct EguiLogger {
     /// will set focus to the input area, and set this value to false.
     #[serde(skip)]
     pub should_focus_input: bool,
+    // Height of the input area when it was last shown, which the UI reserves for it.
+    #[serde(skip)]
+    input_area_height: Option<f32>,
     // The current user input.
     input_text: String,
     // When input_text is empty, this text is displayed to indicate where the input box is.
@@ -92,6 +223,49 @@ pub struct EguiLogger {
     input_categories: Vec<String>,
     // The log level to apply to LogRecords triggered by user input.
     pub input_level: LogLevel,
+    // Channels the user can pick from next to the input area. See `set_input_channels`.
+    input_channels: Vec<InputChannel>,
+    // Name of the picked input channel. Kept so the choice is remembered with the settings.
+    selected_input_channel: Option<String>,
+    /// Levels the user can pick from next to the input area. If empty, no level picker is shown.
+    pub input_levels: Vec<LogLevel>,
+    /// Whether the input area accepts multiple lines. Shift+Enter inserts a newline, and Enter
+    /// submits the input.
+    pub multiline_input: bool,
+    /// Maximum number of lines the input area grows to in `multiline_input` mode, including lines
+    /// that wrap. Beyond this, the input area scrolls.
+    pub max_input_lines: usize,
+    /// Whether submitted input is logged (with `input_text_prefix`, `input_categories` and
+    /// `input_level`). Disable this if the app handles the input itself, e.g. by sending it to a
+    /// server which sends it back as a chat message.
+    pub echo_input: bool,
+    // Inputs submitted since the app last called `take_submitted_inputs`.
+    #[serde(skip)]
+    submitted_inputs: VecDeque<SubmittedInput>,
+    /// Whether input starting with `/` is run as a command. See [`Self::register_command`].
+    pub enable_commands: bool,
+    // Commands that can be run from the input area. Handlers can't be serialized, so these need
+    // to be registered again after loading the logger.
+    #[serde(skip)]
+    commands: CommandRegistry,
+    // Console variables, by name. See `register_cvar`.
+    cvars: BTreeMap<String, Cvar>,
+    // Tab completion state for the input area. `None` when there are no suggestions to show.
+    #[serde(skip)]
+    completion: Option<Completion>,
+    // Previously submitted inputs, newest first. Recalled with Up/Down in the input area.
+    input_history: VecDeque<String>,
+    /// Maximum number of entries kept in the input history. 0 disables the history.
+    pub max_input_history: usize,
+    /// Whether Up/Down only recall history entries that start with the text typed so far.
+    /// Ctrl+R always searches this way.
+    pub history_prefix_search: bool,
+    // Index into `input_history` of the entry being shown. `None` when editing a new input.
+    #[serde(skip)]
+    history_position: Option<usize>,
+    // What was typed before navigating the history, restored when navigating past the newest entry.
+    #[serde(skip)]
+    history_draft: String,
 }
 
 impl Default for EguiLogger {
@@ -100,6 +274,20 @@ impl Default for EguiLogger {
     }
 }
 
+impl Serialize for EguiLogger {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EguiLogger {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut logger = Self::deserialize(deserializer)?;
+        logger.repair_after_load();
+        Ok(logger)
+    }
+}
+
 fn default_records() -> HashMap<LogLevel, VecDeque<LogRecord>> {
     let mut records = HashMap::new();
     records.insert(LogLevel::Error, VecDeque::new());
@@ -115,30 +303,97 @@ impl EguiLogger {
             records: default_records(),
             show_settings: true,
             category_counts: Default::default(),
+            min_capture_level: LogLevel::Debug,
             min_display_level: LogLevel::Debug,
             hidden_categories: HashSet::new(),
             time_format: TimeFormat::LocalTime,
             time_precision: TimePrecision::Seconds,
+            time_zone: DisplayZone::Local,
+            custom_time_format: "%Y-%m-%d %H:%M:%S".to_string(),
+            start_time: Local::now(),
+            app_time_display: AppTimeDisplay::Alongside,
+            time_sources: TimeSources::default(),
             show_categories: true,
             show_level: true,
             show_search: false,
             should_focus_search: false,
             max_message_length: 2000,
             max_records_per_level: 2000,
+            max_total_bytes: None,
+            memory_usage: 0,
+            evicted: HashMap::new(),
+            dropped: HashMap::new(),
+            show_evicted_marker: true,
+            archive: None,
+            collapse_repeats: RepeatCollapse::Off,
+            last_logged_level: None,
+            category_rate_limits: HashMap::new(),
+            level_rate_limits: HashMap::new(),
+            rate_limiters: HashMap::new(),
             search_term: String::new(),
             search_regex: None,
             search_with_regex: false,
             search_with_case_sensitive: false,
+            show_only_bookmarked: false,
+            time_range: None,
+            follow_newest: true,
+            next_record_id: 0,
+            scrolled_to_bottom: true,
+            first_unread_id: None,
+            scroll_to_newest: false,
+            paused_records: None,
+            paused_at_id: 0,
+            selected_ids: BTreeSet::new(),
+            selection_anchor: None,
+            selection_cursor: None,
+            scroll_to_selection: false,
+            layout: LogLayout::Text,
+            table_columns: vec![
+                TableColumn::new(LogColumn::Time, 100.0),
+                TableColumn::new(LogColumn::Level, 60.0),
+                TableColumn::new(LogColumn::Categories, 120.0),
+                TableColumn::new(LogColumn::Message, 300.0),
+            ],
+            sort_column: None,
+            sort_descending: false,
+            select_text: false,
+            show_details: false,
+            show_timeline: false,
+            details_position: DetailsPosition::Right,
+            details_size: 300.0,
+            tabs: Vec::new(),
+            active_tab: 0,
             show_input_area: true,
             should_focus_input: false,
+            input_area_height: None,
             input_hint: "Type a message and press Enter...".to_string(),
             input_text: String::new(),
             input_text_prefix: String::new(),
             input_categories: vec!["Input".parse().unwrap()],
             input_level: LogLevel::Info,
+            input_channels: Vec::new(),
+            selected_input_channel: None,
+            input_levels: Vec::new(),
+            multiline_input: false,
+            max_input_lines: 5,
+            echo_input: true,
+            submitted_inputs: VecDeque::new(),
+            enable_commands: true,
+            commands: CommandRegistry::default(),
+            cvars: BTreeMap::new(),
+            completion: None,
+            input_history: VecDeque::new(),
+            max_input_history: 100,
+            history_prefix_search: false,
+            history_position: None,
+            history_draft: String::new(),
         }
     }
     pub fn show(&mut self, ui: &mut egui::Ui) {
+        // Rate limits only see time pass when records are logged, so check here as well in order to
+        // report the end of suppression even if the category has gone quiet.
+        self.end_expired_suppressions(self.now().fixed_offset());
+        self.save_active_tab();
         crate::ui::render_logger_ui(self, ui);
     }
 
@@ -158,13 +413,110 @@ impl EguiLogger {
         self.log(LogLevel::Debug, categories, message);
     }
 
-    /// Log a message with the given level and category
+    pub fn log_error_with<C: IntoCategories, M: std::fmt::Display>(
+        &mut self,
+        categories: C,
+        message: impl FnOnce() -> M,
+    ) {
+        self.log_with(LogLevel::Error, categories, message);
+    }
+
+    pub fn log_warn_with<C: IntoCategories, M: std::fmt::Display>(
+        &mut self,
+        categories: C,
+        message: impl FnOnce() -> M,
+    ) {
+        self.log_with(LogLevel::Warn, categories, message);
+    }
+
+    pub fn log_info_with<C: IntoCategories, M: std::fmt::Display>(
+        &mut self,
+        categories: C,
+        message: impl FnOnce() -> M,
+    ) {
+        self.log_with(LogLevel::Info, categories, message);
+    }
+
+    pub fn log_debug_with<C: IntoCategories, M: std::fmt::Display>(
+        &mut self,
+        categories: C,
+        message: impl FnOnce() -> M,
+    ) {
+        self.log_with(LogLevel::Debug, categories, message);
+    }
+
+    /// Whether records of the given level are stored, based on `min_capture_level`.
+    pub fn is_captured(&self, level: LogLevel) -> bool {
+        level >= self.min_capture_level
+    }
+
+    /// Like [`Self::log`], but the message is only built if the record will be stored.
+    /// Useful for expensive messages in hot loops, e.g.
+    /// `logger.log_debug_with(Category::Physics, || format!("{:?}", world))`.
+    pub fn log_with<C: IntoCategories, M: std::fmt::Display>(
+        &mut self,
+        level: LogLevel,
+        categories: C,
+        message: impl FnOnce() -> M,
+    ) {
+        if !self.is_captured(level) {
+            return;
+        }
+        self.log(level, categories, message());
+    }
+
+    /// Log a message with the given level and category.
+    /// Records below `min_capture_level` are dropped without formatting the message.
     pub fn log<C: IntoCategories, M: std::fmt::Display>(&mut self, level: LogLevel, categories: C, message: M) {
-        let mut record = Self::get_log_record(level, categories, message);
+        if !self.is_captured(level) {
+            return;
+        }
+        let mut record = self.new_log_record(level, categories, message);
         self.clean_record(&mut record);
         self.log_record(record);
     }
 
+    /// Like [`Self::get_log_record`], but timestamped by the logger's clock, with its app time.
+    pub fn new_log_record<C: IntoCategories, M: std::fmt::Display>(
+        &self,
+        level: LogLevel,
+        categories: C,
+        message: M,
+    ) -> LogRecord {
+        let mut record = Self::get_log_record(level, categories, message);
+        record.timestamp = self.now().fixed_offset();
+        record.app_time = self.time_sources.app_time.as_ref().map(|app_time| app_time());
+        record
+    }
+
+    /// Use `clock` for the timestamps of new records, e.g. a [`crate::ManualClock`] for
+    /// deterministic tests. Also restarts `TimeFormat::SinceStart` from the clock's current time.
+    /// The clock isn't saved with the logger, so it needs to be set again after loading.
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.time_sources.clock = std::sync::Arc::new(clock);
+        self.start_time = self.now();
+    }
+
+    /// The current time according to the logger's clock.
+    pub fn now(&self) -> DateTime<Local> {
+        self.time_sources.clock.now()
+    }
+
+    /// Attach the app's own timestamp to new records, e.g. a frame number or in-game time, shown
+    /// according to `app_time_display`. The function is called once per record.
+    /// It isn't saved with the logger, so it needs to be set again after loading.
+    pub fn set_app_time<F>(&mut self, app_time: F)
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.time_sources.app_time = Some(std::sync::Arc::new(app_time));
+    }
+
+    /// Stop attaching app timestamps to new records.
+    pub fn clear_app_time(&mut self) {
+        self.time_sources.app_time = None;
+    }
+
     /// Modifies the provided LogRecord to conform to the logger.
     fn clean_record(&self, record: &mut LogRecord) {
         if record.message.len() > self.max_message_length {
@@ -172,7 +524,8 @@ impl EguiLogger {
         }
     }
 
-    /// Get a log record. This is the same LogRecord created by calling `log`.
+    /// Get a log record timestamped with the local time, without an app time. Records created by
+    /// `log` use the logger's clock and app time instead; use [`Self::new_log_record`] to get the same.
     /// The record can be pushed into the chat using [`Self::log_record`].
     pub fn get_log_record<C: IntoCategories, M: std::fmt::Display>(
         level: LogLevel,
@@ -186,15 +539,69 @@ impl EguiLogger {
         let cleaned_message: String = message_str.chars().filter(|c| !c.eq(&'\n')).collect();
 
         LogRecord {
-            timestamp: Local::now(),
+            timestamp: Local::now().fixed_offset(),
             level,
             categories: category_strs,
             message: cleaned_message,
+            repeats: 0,
+            last_timestamp: None,
+            id: 0,
+            app_time: None,
+            bookmark: None,
+            pinned: false,
         }
     }
 
     /// Adds a LogRecord to the logs. The provided timestamp is used, so it will show up above existing messages if messages are provided out of order.
+    /// Records below `min_capture_level` are dropped.
     pub fn log_record(&mut self, log_record: LogRecord) {
+        if !self.is_captured(log_record.level) {
+            return;
+        }
+        self.end_expired_suppressions(log_record.timestamp);
+        if !self.admit_by_rate_limits(&log_record) {
+            return;
+        }
+        self.store_record(log_record);
+    }
+
+    /// Restores what older versions didn't save: record ids, which they all load as 0, and the
+    /// memory usage of the records. Also reads the length of the archive file, which isn't saved.
+    fn repair_after_load(&mut self) {
+        if let Some(archive) = &mut self.archive {
+            archive.refresh_file_len();
+        }
+        let mut records: Vec<&mut LogRecord> = self.records.values_mut().flatten().collect();
+        let mut ids: Vec<u64> = records.iter().map(|r| r.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.len() != records.len() || ids.last().is_some_and(|id| *id >= self.next_record_id) {
+            records.sort_by_key(|r| (r.id, r.timestamp));
+            for (id, record) in records.iter_mut().enumerate() {
+                record.id = id as u64;
+            }
+            self.next_record_id = records.len() as u64;
+        }
+        self.memory_usage = self.records.values().flatten().map(|r| r.approximate_size()).sum();
+    }
+
+    /// Stores a record that has passed the capture level and rate limits.
+    fn store_record(&mut self, mut log_record: LogRecord) {
+        if let Some(previous) = self.find_repeat_target(&log_record) {
+            previous.repeats += 1 + log_record.repeats;
+            previous.last_timestamp = Some(previous.latest_timestamp().max(log_record.latest_timestamp()));
+            self.last_logged_level = Some(log_record.level);
+            return;
+        }
+        self.last_logged_level = Some(log_record.level);
+        log_record.id = self.next_record_id;
+        self.next_record_id += 1;
+        // Records that arrive while the user is reading older ones haven't been seen yet.
+        let following = self.follow_newest && self.scrolled_to_bottom && !self.is_paused();
+        if !following && self.first_unread_id.is_none() {
+            self.first_unread_id = Some(log_record.id);
+        }
+
         log_record.categories.iter().for_each(|category| {
             self.category_counts
                 .entry(category.to_string())
@@ -202,55 +609,668 @@ impl EguiLogger {
                 .or_insert(1);
         });
 
+        self.count_unread(&log_record);
+        self.memory_usage += log_record.approximate_size();
         self.records.get_mut(&log_record.level).unwrap().push_back(log_record);
 
         self.enforce_limits();
     }
 
+    /// Counts a new record as unread in the tabs other than the active one.
+    fn count_unread(&mut self, record: &LogRecord) {
+        if self.tabs.len() < 2 {
+            return;
+        }
+        // Formatting is only needed for tabs with a search term, and then only once.
+        let text = match self.tabs.iter().any(|tab| !tab.search_term.is_empty()) {
+            true => self.format_record_text(record),
+            false => String::new(),
+        };
+        for (i, tab) in self.tabs.iter_mut().enumerate() {
+            if i != self.active_tab {
+                tab.count_unread(record, &text);
+            }
+        }
+    }
+
+    /// Limit how many records with the given category are stored per second. `None` removes the limit.
+    pub fn set_category_rate_limit<T: ToString>(&mut self, category: T, limit: Option<RateLimit>) {
+        let category = category.to_string();
+        match limit {
+            Some(limit) => self.category_rate_limits.insert(category.clone(), limit),
+            None => self.category_rate_limits.remove(&category),
+        };
+        self.rate_limiters.remove(&RateLimitKey::Category(category));
+    }
+
+    /// Limit how many records of the given level are stored per second. `None` removes the limit.
+    pub fn set_level_rate_limit(&mut self, level: LogLevel, limit: Option<RateLimit>) {
+        match limit {
+            Some(limit) => self.level_rate_limits.insert(level, limit),
+            None => self.level_rate_limits.remove(&level),
+        };
+        self.rate_limiters.remove(&RateLimitKey::Level(level));
+    }
+
+    /// Suppression counters for the rate limit on the given category, if it has seen any records.
+    pub fn category_rate_limit_stats<T: ToString>(&self, category: T) -> Option<RateLimitStats> {
+        self.rate_limiters
+            .get(&RateLimitKey::Category(category.to_string()))
+            .map(|l| l.stats)
+    }
+
+    /// Suppression counters for the rate limit on the given level, if it has seen any records.
+    pub fn level_rate_limit_stats(&self, level: LogLevel) -> Option<RateLimitStats> {
+        self.rate_limiters.get(&RateLimitKey::Level(level)).map(|l| l.stats)
+    }
+
+    fn rate_limit(&self, key: &RateLimitKey) -> Option<&RateLimit> {
+        match key {
+            RateLimitKey::Category(category) => self.category_rate_limits.get(category),
+            RateLimitKey::Level(level) => self.level_rate_limits.get(level),
+        }
+    }
+
+    /// Counts the record against every rate limit that applies to it, and returns whether it
+    /// is within all of them.
+    fn admit_by_rate_limits(&mut self, record: &LogRecord) -> bool {
+        let keys = record
+            .categories
+            .iter()
+            .map(|c| RateLimitKey::Category(c.clone()))
+            .chain(std::iter::once(RateLimitKey::Level(record.level)));
+        let mut admitted = true;
+        for key in keys {
+            let Some(limit) = self.rate_limit(&key).copied() else {
+                continue;
+            };
+            admitted &= self.rate_limiters.entry(key).or_default().admit(&limit);
+        }
+        admitted
+    }
+
+    /// Logs a summary record for each rate limit whose suppression has ended by `now`.
+    fn end_expired_suppressions(&mut self, now: DateTime<FixedOffset>) {
+        let mut ended = Vec::new();
+        for (key, limiter) in self.rate_limiters.iter_mut() {
+            let limit = match key {
+                RateLimitKey::Category(category) => self.category_rate_limits.get(category),
+                RateLimitKey::Level(level) => self.level_rate_limits.get(level),
+            };
+            if let Some(count) = limit.and_then(|limit| limiter.roll_window(limit, now)) {
+                ended.push((key.clone(), count));
+            }
+        }
+        for (key, count) in ended {
+            let noun = if count == 1 { "message" } else { "messages" };
+            let (level, categories, source) = match key {
+                RateLimitKey::Category(category) => (LogLevel::Warn, vec![category.clone()], category),
+                RateLimitKey::Level(level) => (level, Vec::new(), format!("level {}", level.as_str())),
+            };
+            let mut record =
+                Self::get_log_record(level, categories, format!("Suppressed {count} {noun} from {source}"));
+            // Just before `now`, so it's listed before the record that ended the suppression.
+            record.timestamp = now - TimeDelta::nanoseconds(1);
+            self.store_record(record);
+        }
+    }
+
+    /// Finds the stored record that `record` should be merged into, based on `collapse_repeats`.
+    fn find_repeat_target(&mut self, record: &LogRecord) -> Option<&mut LogRecord> {
+        let records = self.records.get_mut(&record.level).unwrap();
+        match self.collapse_repeats {
+            RepeatCollapse::Off => None,
+            RepeatCollapse::Consecutive => {
+                if self.last_logged_level != Some(record.level) {
+                    return None;
+                }
+                records.back_mut().filter(|r| r.is_repeat_of(record))
+            }
+            RepeatCollapse::Within(window) => {
+                let window = chrono::TimeDelta::from_std(window).unwrap_or(chrono::TimeDelta::MAX);
+                records
+                    .iter_mut()
+                    .rev()
+                    .take_while(|r| record.timestamp - r.latest_timestamp() <= window)
+                    .find(|r| r.is_repeat_of(record))
+            }
+        }
+    }
+
+    /// Removes the oldest record of the given level, keeping the bookkeeping of the logger in sync.
+    fn evict_oldest(&mut self, level: &LogLevel) -> Option<LogRecord> {
+        let r = self.records.get_mut(level).unwrap().pop_front()?;
+        r.categories.iter().for_each(|category| {
+            self.category_counts
+                .entry(category.to_string())
+                .and_modify(|count| *count -= 1);
+        });
+        self.memory_usage = self.memory_usage.saturating_sub(r.approximate_size());
+        self.evicted
+            .entry(r.level)
+            .and_modify(|e| e.add(&r))
+            .or_insert_with(|| EvictedRecords::new(&r));
+        let archived = self.archive.as_mut().is_some_and(|archive| archive.append(&r));
+        if !archived {
+            self.dropped
+                .entry(r.level)
+                .and_modify(|e| e.add(&r))
+                .or_insert_with(|| EvictedRecords::new(&r));
+        }
+        Some(r)
+    }
+
     /// Enforce the maximum record limits for a single log level.
     fn enforce_limit(&mut self, level: &LogLevel) {
-        let records = self.records.get_mut(level).unwrap();
-        while records.len() > self.max_records_per_level {
-            if let Some(r) = records.pop_front() {
-                r.categories.iter().for_each(|category| {
-                    self.category_counts
-                        .entry(category.to_string())
-                        .and_modify(|count| *count -= 1);
-                })
+        while self.records[level].len() > self.max_records_per_level {
+            self.evict_oldest(level);
+        }
+    }
+
+    /// Enforce the total memory budget, evicting the oldest records of the lowest levels first.
+    fn enforce_memory_budget(&mut self) {
+        let Some(max_total_bytes) = self.max_total_bytes else {
+            return;
+        };
+        for level in [LogLevel::Debug, LogLevel::Info, LogLevel::Warn, LogLevel::Error] {
+            while self.memory_usage > max_total_bytes {
+                if self.evict_oldest(&level).is_none() {
+                    break;
+                }
             }
         }
     }
 
-    /// Enforce the maximum record limits per level
+    /// Enforce the maximum record limits per level, and the total memory budget.
     fn enforce_limits(&mut self) {
         self.enforce_limit(&LogLevel::Error);
         self.enforce_limit(&LogLevel::Warn);
         self.enforce_limit(&LogLevel::Info);
         self.enforce_limit(&LogLevel::Debug);
+        self.enforce_memory_budget();
     }
 
     // Categories to apply to LogRecords triggered by user input.
+    // This deselects the input channel, whose categories no longer apply.
     pub fn set_input_categories<T: ToString>(&mut self, categories: Vec<T>) {
         self.input_categories = categories.into_iter().map(|c| c.to_string()).collect();
+        self.selected_input_channel = None;
+    }
+
+    /// Channels the user can pick from next to the input area. Picking one sets the input
+    /// categories. The previously picked channel is kept if it's still available, otherwise the
+    /// first one is picked. An empty list hides the picker.
+    pub fn set_input_channels(&mut self, channels: Vec<InputChannel>) {
+        self.input_channels = channels;
+        let previous = self.selected_input_channel.take();
+        let name = previous
+            .filter(|name| self.input_channels.iter().any(|c| &c.name == name))
+            .or_else(|| self.input_channels.first().map(|c| c.name.clone()));
+        if let Some(name) = name {
+            self.select_input_channel(&name);
+        }
+    }
+
+    pub fn input_channels(&self) -> &[InputChannel] {
+        &self.input_channels
+    }
+
+    /// Pick the input channel with the given name. Returns false if there is no such channel.
+    pub fn select_input_channel(&mut self, name: &str) -> bool {
+        let Some(channel) = self.input_channels.iter().find(|c| c.name == name) else {
+            return false;
+        };
+        self.input_categories = channel.categories.clone();
+        self.selected_input_channel = Some(channel.name.clone());
+        true
+    }
+
+    pub fn selected_input_channel(&self) -> Option<&str> {
+        self.selected_input_channel.as_deref()
+    }
+
+    /// Register a command that can be run from the input area by typing `/name`.
+    /// Replaces any existing command with the same name, including the built-in `/help`.
+    pub fn register_command(&mut self, command: Command) {
+        self.commands.register(command);
+    }
+
+    pub fn unregister_command(&mut self, name: &str) -> Option<Command> {
+        self.commands.unregister(name)
+    }
+
+    /// Registered commands, sorted by name. Doesn't include the built-in `/help`.
+    pub fn commands(&self) -> impl Iterator<Item = &Command> {
+        self.commands.iter()
+    }
+
+    /// Handle text submitted through the input area. Commands are run if `enable_commands` is set.
+    /// Anything else is queued for [`Self::take_submitted_inputs`], and logged if `echo_input` is set.
+    pub fn submit_input(&mut self, text: &str) {
+        self.add_input_history(text);
+        if self.enable_commands
+            && let Some(command_line) = text.trim_start().strip_prefix('/')
+        {
+            self.run_command(command_line);
+            return;
+        }
+        self.submitted_inputs.push_back(SubmittedInput {
+            text: text.to_string(),
+            level: self.input_level,
+            categories: self.input_categories.clone(),
+        });
+        while self.submitted_inputs.len() > MAX_SUBMITTED_INPUTS {
+            self.submitted_inputs.pop_front();
+        }
+        if !self.echo_input {
+            return;
+        }
+        let prefix_text: String = self.input_text_prefix.chars().take(128).collect();
+        // Records are single lines, so multi-line input is logged as one record per line, with the
+        // prefix on the first one.
+        let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+        for (i, line) in lines.into_iter().enumerate() {
+            let prefix = if i == 0 { prefix_text.as_str() } else { "" };
+            self.log(
+                self.input_level,
+                self.input_categories.clone(),
+                format!("{}{}", prefix, line),
+            );
+        }
+    }
+
+    /// Takes the inputs submitted since the last call, oldest first. Call this every frame after
+    /// [`Self::show`] to handle what the user typed, e.g. to send it over the network as chat.
+    pub fn take_submitted_inputs(&mut self) -> Vec<SubmittedInput> {
+        self.submitted_inputs.drain(..).collect()
+    }
+
+    /// Previously submitted inputs, newest first.
+    pub fn input_history(&self) -> impl Iterator<Item = &str> {
+        self.input_history.iter().map(|s| s.as_str())
+    }
+
+    pub fn clear_input_history(&mut self) {
+        self.input_history.clear();
+        self.reset_history_navigation();
+    }
+
+    fn add_input_history(&mut self, text: &str) {
+        self.reset_history_navigation();
+        if text.trim().is_empty() || self.input_history.front().is_some_and(|last| last == text) {
+            return;
+        }
+        self.input_history.push_front(text.to_string());
+        self.input_history.truncate(self.max_input_history);
+    }
+
+    /// Stop navigating the history, e.g. because the user edited the recalled text.
+    pub(crate) fn reset_history_navigation(&mut self) {
+        self.history_position = None;
+    }
+
+    fn history_matches(&self, index: usize, prefix_search: bool) -> bool {
+        !prefix_search || self.input_history[index].starts_with(&self.history_draft)
+    }
+
+    /// Replace the input text with the previous (older) history entry.
+    /// If `prefix_search` is set, only entries starting with the originally typed text are used.
+    /// Returns false if there is no older entry.
+    pub(crate) fn history_previous(&mut self, prefix_search: bool) -> bool {
+        if self.history_position.is_none() {
+            self.history_draft = self.input_text.clone();
+        }
+        let start = self.history_position.map_or(0, |p| p + 1);
+        let Some(index) = (start..self.input_history.len()).find(|i| self.history_matches(*i, prefix_search)) else {
+            return false;
+        };
+        self.history_position = Some(index);
+        self.input_text = self.input_history[index].clone();
+        true
+    }
+
+    /// Replace the input text with the next (newer) history entry, or with what was originally
+    /// typed once past the newest entry. Returns false if not navigating the history.
+    pub(crate) fn history_next(&mut self, prefix_search: bool) -> bool {
+        let Some(position) = self.history_position else {
+            return false;
+        };
+        match (0..position).rev().find(|i| self.history_matches(*i, prefix_search)) {
+            Some(index) => {
+                self.history_position = Some(index);
+                self.input_text = self.input_history[index].clone();
+            }
+            None => {
+                self.history_position = None;
+                self.input_text = std::mem::take(&mut self.history_draft);
+            }
+        }
+        true
+    }
+
+    /// Run a command line such as `teleport 10 20`, without the leading `/`.
+    /// Errors are logged with `input_categories`.
+    pub fn run_command(&mut self, command_line: &str) {
+        let mut tokens = tokenize(command_line);
+        if tokens.is_empty() {
+            return;
+        }
+        let name = tokens.remove(0);
+        if self.builtin_commands().any(|(builtin, _, _)| builtin == name) {
+            self.run_builtin_command(&name, &tokens);
+            return;
+        }
+        let result = self
+            .commands
+            .prepare(&name, tokens)
+            .and_then(|(handler, args)| handler(self, &args));
+        if let Err(e) = result {
+            self.log_error(self.input_categories.clone(), format!("/{}: {}", name, e));
+        }
+    }
+
+    /// Built-in commands as (name, usage, description). A registered command with the same name
+    /// replaces the built-in one. The console variable commands are only available once a cvar
+    /// has been registered.
+    fn builtin_commands(&self) -> impl Iterator<Item = (&'static str, &'static str, &'static str)> {
+        let has_cvars = !self.cvars.is_empty();
+        [
+            ("help", "/help [command]", "Show this list", true),
+            ("get", "/get <cvar>", "Show the value of a console variable", has_cvars),
+            ("set", "/set <cvar> <value>", "Change a console variable", has_cvars),
+            (
+                "reset",
+                "/reset <cvar>",
+                "Reset a console variable to its default",
+                has_cvars,
+            ),
+            ("list", "/list [filter]", "List console variables", has_cvars),
+        ]
+        .into_iter()
+        .filter(|(name, _, _, available)| *available && self.commands.get(name).is_none())
+        .map(|(name, usage, description, _)| (name, usage, description))
+    }
+
+    fn run_builtin_command(&mut self, name: &str, args: &[String]) {
+        let arg = |i: usize| args.get(i).map(|s| s.as_str());
+        let result = match (name, arg(0)) {
+            ("help", command) => {
+                self.log_help(command);
+                Ok(())
+            }
+            ("list", filter) => {
+                self.log_cvars(filter.unwrap_or_default());
+                Ok(())
+            }
+            ("get", Some(cvar)) => match self.cvars.get(cvar).map(|c| c.summary()) {
+                Some(summary) => {
+                    self.log_info(self.input_categories.clone(), summary);
+                    Ok(())
+                }
+                None => Err(unknown_cvar(cvar)),
+            },
+            ("set", Some(cvar)) if args.len() >= 2 => self.set_cvar_from_input(cvar, &args[1..].join(" ")),
+            ("reset", Some(cvar)) => self.reset_cvar(cvar),
+            _ => {
+                let usage = self.builtin_commands().find(|(n, _, _)| *n == name).map(|(_, u, _)| u);
+                Err(format!("Usage: {}", usage.unwrap_or_default()))
+            }
+        };
+        if let Err(e) = result {
+            self.log_error(self.input_categories.clone(), format!("/{}: {}", name, e));
+        }
+    }
+
+    /// The built-in `/help [command]`.
+    fn log_help(&mut self, command: Option<&str>) {
+        let builtins: Vec<String> = self
+            .builtin_commands()
+            .map(|(_, usage, description)| format!("{} - {}", usage, description))
+            .collect();
+        let lines: Vec<String> = match command.map(|c| c.trim_start_matches('/')) {
+            Some(name) => match self.commands.get(name) {
+                Some(c) => vec![format!("{} - {}", c.usage(), c.description())],
+                None => match self.builtin_commands().position(|(n, _, _)| n == name) {
+                    Some(i) => vec![builtins[i].clone()],
+                    None => vec![format!("Unknown command '/{}'.", name)],
+                },
+            },
+            None => std::iter::once("Available commands:".to_string())
+                .chain(
+                    self.commands
+                        .iter()
+                        .map(|c| format!("{} - {}", c.usage(), c.description()))
+                        .chain(builtins)
+                        .map(|line| format!("  {}", line)),
+                )
+                .collect(),
+        };
+        for line in lines {
+            self.log_info(self.input_categories.clone(), line);
+        }
+    }
+
+    /// The built-in `/list [filter]`.
+    fn log_cvars(&mut self, filter: &str) {
+        let filter = filter.to_lowercase();
+        let lines: Vec<String> = self
+            .cvars
+            .values()
+            .filter(|c| c.name().to_lowercase().contains(&filter))
+            .map(|c| c.summary())
+            .collect();
+        if lines.is_empty() {
+            self.log_info(self.input_categories.clone(), "No matching console variables.");
+        }
+        for line in lines {
+            self.log_info(self.input_categories.clone(), line);
+        }
+    }
+
+    /// Register a console variable. If a cvar with this name was loaded along with the logger's
+    /// settings, its value is kept as long as it's still valid.
+    pub fn register_cvar(&mut self, mut cvar: Cvar) {
+        if let Some(existing) = self.cvars.get(cvar.name())
+            && cvar.validate(existing.value()).is_ok()
+        {
+            cvar.replace(existing.value().clone());
+        }
+        self.cvars.insert(cvar.name().to_string(), cvar);
+    }
+
+    pub fn unregister_cvar(&mut self, name: &str) -> Option<Cvar> {
+        self.cvars.remove(name)
+    }
+
+    pub fn cvar(&self, name: &str) -> Option<&Cvar> {
+        self.cvars.get(name)
+    }
+
+    /// Registered console variables, sorted by name.
+    pub fn cvars(&self) -> impl Iterator<Item = &Cvar> {
+        self.cvars.values()
+    }
+
+    pub fn cvar_value(&self, name: &str) -> Option<&CvarValue> {
+        self.cvars.get(name).map(|c| c.value())
+    }
+
+    /// Change the value of a console variable, logging the change like `/set` does. Fails if the
+    /// cvar doesn't exist, or the value has the wrong type or is out of range.
+    pub fn set_cvar(&mut self, name: &str, value: impl Into<CvarValue>) -> Result<(), String> {
+        let cvar = self.cvars.get_mut(name).ok_or_else(|| unknown_cvar(name))?;
+        let value = value.into();
+        cvar.validate(&value)?;
+        let previous = cvar.replace(value.clone());
+        self.log_info(
+            self.input_categories.clone(),
+            format!("{} = {} (was {})", name, value, previous),
+        );
+        Ok(())
+    }
+
+    /// Reset a console variable to its default value, logging the change like `/reset` does.
+    pub fn reset_cvar(&mut self, name: &str) -> Result<(), String> {
+        let cvar = self.cvars.get(name).ok_or_else(|| unknown_cvar(name))?;
+        let default = cvar.default_value().clone();
+        self.set_cvar(name, default)
+    }
+
+    fn set_cvar_from_input(&mut self, name: &str, text: &str) -> Result<(), String> {
+        let cvar = self.cvars.get(name).ok_or_else(|| unknown_cvar(name))?;
+        let value = cvar.parse(text)?;
+        self.set_cvar(name, value)
     }
 
     /// Clear all log records
     pub fn clear(&mut self) {
         self.records.iter_mut().for_each(|(_, r)| r.clear());
         self.category_counts.clear();
+        self.memory_usage = 0;
+        self.evicted.clear();
+        self.dropped.clear();
+        self.rate_limiters.clear();
+        if let Some(archive) = &mut self.archive {
+            archive.clear_loaded();
+        }
+        self.tabs.iter_mut().for_each(LogTab::mark_read);
+        self.first_unread_id = None;
+        if let Some(paused) = &mut self.paused_records {
+            paused.clear();
+            self.paused_at_id = self.next_record_id;
+        }
+        self.clear_selection();
     }
 
-    /// Get all records that match current filters
+    /// Start appending evicted records to the file at `path`, creating it if necessary.
+    /// Existing contents are kept, so the archive can be shared across sessions.
+    pub fn enable_archive<P: Into<PathBuf>>(&mut self, path: P) {
+        self.archive = Some(LogArchive::new(path));
+    }
+
+    /// Stop archiving evicted records. Records that were loaded from the archive are removed from
+    /// the view, but the archive file is left untouched.
+    pub fn disable_archive(&mut self) {
+        self.archive = None;
+    }
+
+    pub fn archive(&self) -> Option<&LogArchive> {
+        self.archive.as_ref()
+    }
+
+    pub fn archive_mut(&mut self) -> Option<&mut LogArchive> {
+        self.archive.as_mut()
+    }
+
+    /// Read the next page of older records from the archive into the view.
+    /// Loaded records don't count towards [`Self::max_total_bytes`] and stay in memory until
+    /// [`Self::clear`] or [`Self::disable_archive`] is called.
+    /// Returns the number of records loaded, which is 0 if archiving isn't enabled or the view is
+    /// paused, since the loaded records wouldn't be shown.
+    pub fn load_older_archived(&mut self) -> std::io::Result<usize> {
+        if self.is_paused() {
+            return Ok(0);
+        }
+        match &mut self.archive {
+            Some(archive) => archive.load_older(&mut self.next_record_id),
+            None => Ok(0),
+        }
+    }
+
+    /// All records, including those loaded back from the archive, sorted by timestamp.
+    /// While paused, these are the records from when the view was paused.
+    pub(crate) fn sorted_records(&self) -> Vec<&LogRecord> {
+        if let Some(paused) = &self.paused_records {
+            return paused.iter().collect();
+        }
+        let archived = self.archive.iter().flat_map(|a| a.loaded());
+        let mut records: Vec<&LogRecord> = archived.chain(self.records.values().flatten()).collect();
+        // Records are kept per level, so ties are broken by id to keep the order they were logged in.
+        records.sort_by_key(|r| (r.timestamp, r.id));
+        records
+    }
+
+    /// Get all records that match current filters, including those loaded back from the archive.
     pub fn filtered_records(&self) -> Vec<&LogRecord> {
-        self.records
-            .values()
-            .flatten()
+        self.sorted_records()
+            .into_iter()
             .filter(|record| self.matches_filters(record))
             .collect()
     }
 
+    /// Records that match the current filters, in the order they're displayed. In the table
+    /// layout, this follows the sort column.
+    pub(crate) fn displayed_records(&self) -> Vec<&LogRecord> {
+        let mut records = self.filtered_records();
+        if self.layout == LogLayout::Table
+            && let Some(column) = self.sort_column
+        {
+            // Stable, so records with equal values stay in chronological order.
+            records.sort_by(|a, b| match self.sort_descending {
+                true => column.compare(b, a),
+                false => column.compare(a, b),
+            });
+        }
+        records
+    }
+
+    /// Sort the table layout by `column`, cycling through ascending, descending and unsorted.
+    pub(crate) fn toggle_table_sort(&mut self, column: LogColumn) {
+        match self.sort_column {
+            Some(current) if current == column && !self.sort_descending => self.sort_descending = true,
+            Some(current) if current == column => self.sort_column = None,
+            _ => {
+                self.sort_column = Some(column);
+                self.sort_descending = false;
+            }
+        }
+    }
+
+    /// Counts of the records matching the filters over time, split into `bins`. The time range
+    /// filter is ignored, so the timeline shows what's around the range.
+    pub(crate) fn timeline(&self, bins: usize) -> Option<Timeline> {
+        let records: Vec<&LogRecord> = self
+            .sorted_records()
+            .into_iter()
+            .filter(|r| self.matches_filters_except_time_range(r))
+            .collect();
+        Timeline::new(&records, bins)
+    }
+
+    /// Select and scroll to the first displayed record logged at or after `time`, or the last
+    /// one if they're all older. Returns false if no records are displayed.
+    pub fn jump_to_time(&mut self, time: DateTime<FixedOffset>) -> bool {
+        let records = self.filtered_records();
+        let Some(record) = records.iter().find(|r| r.timestamp >= time).or(records.last()) else {
+            return false;
+        };
+        let id = record.id;
+        self.jump_to_record(id);
+        true
+    }
+
+    /// Text of a record's cell in the table layout.
+    pub(crate) fn column_text(&self, record: &LogRecord, column: LogColumn) -> String {
+        match column {
+            LogColumn::Time => self.format_record_time(record).trim_end().to_string(),
+            LogColumn::Level => record.level.as_str().to_string(),
+            LogColumn::Categories => record.categories.join(", "),
+            LogColumn::Message => format!("{}{}", record.message, self.format_repeats(record)),
+        }
+    }
+
     /// Check if a record matches current filters
     pub(crate) fn matches_filters(&self, record: &LogRecord) -> bool {
+        if let Some(range) = &self.time_range
+            && !range.contains(record.timestamp, self.now().fixed_offset())
+        {
+            return false;
+        }
+        self.matches_filters_except_time_range(record)
+    }
+
+    /// Like [`Self::matches_filters`], but ignoring `time_range`.
+    fn matches_filters_except_time_range(&self, record: &LogRecord) -> bool {
         // Level filtering (show this level and more severe)
         if record.level < self.min_display_level {
             return false;
@@ -265,23 +1285,26 @@ impl EguiLogger {
             }
         }
 
+        if let Some(tab) = self.tabs.get(self.active_tab)
+            && !tab.includes_categories(record)
+        {
+            return false;
+        }
+
+        if self.show_only_bookmarked && record.bookmark.is_none() {
+            return false;
+        }
+
         // Search filtering
         if !self.search_term.is_empty() {
             let formatted = self.format_record_text(record);
-            let matches = if self.search_with_regex {
-                // Note that the regex itself is generated to be case sensitive or not, so
-                // that the regex + case check doesn't need to happen here.
-                if self.search_regex.is_none() {
-                    true
-                } else {
-                    self.search_regex.as_ref().unwrap().is_match(&formatted)
-                }
-            } else if self.search_with_case_sensitive {
-                formatted.contains(&self.search_term)
-            } else {
-                formatted.to_lowercase().contains(&self.search_term.to_lowercase())
-            };
-            if !matches {
+            if !search_matches(
+                &formatted,
+                &self.search_term,
+                self.search_regex.as_ref(),
+                self.search_with_regex,
+                self.search_with_case_sensitive,
+            ) {
                 return false;
             }
         }
@@ -304,11 +1327,33 @@ impl EguiLogger {
 
         let time_str = format!(
             "{: >width$}",
-            self.format_time(record.timestamp),
+            self.format_record_time(record),
             width = self.get_time_format_padding()
         );
 
-        format!("{}{}{}{}", time_str, level_str, category_str, record.message)
+        format!(
+            "{}{}{}{}{}",
+            time_str,
+            level_str,
+            category_str,
+            record.message,
+            self.format_repeats(record)
+        )
+    }
+
+    /// Suffix for records that have merged repeats, e.g. " [×37, last 12:00:05]".
+    /// Empty if the record has no repeats.
+    pub(crate) fn format_repeats(&self, record: &LogRecord) -> String {
+        if record.repeats == 0 {
+            return String::new();
+        }
+        let count = record.repeats + 1;
+        match record.last_timestamp {
+            Some(last) if self.time_format != TimeFormat::Hide => {
+                format!(" [×{}, last {}]", count, self.format_time(last).trim_end())
+            }
+            _ => format!(" [×{}]", count),
+        }
     }
 
     /// Get all unique categories that have been logged
@@ -321,12 +1366,78 @@ impl EguiLogger {
         self.records.values().map(|r| r.len()).sum()
     }
 
+    /// Approximate number of bytes used by all stored records.
+    /// This is the value that is compared against `max_total_bytes`.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
+    /// Number of records of the given level that have been evicted to stay within
+    /// `max_records_per_level` or `max_total_bytes`. Reset by [`Self::clear`].
+    pub fn evicted_count(&self, level: LogLevel) -> u64 {
+        self.evicted.get(&level).map_or(0, |e| e.count)
+    }
+
+    /// Total number of records evicted across all levels. Reset by [`Self::clear`].
+    pub fn total_evicted(&self) -> u64 {
+        self.evicted.values().map(|e| e.count).sum()
+    }
+
+    /// Details about the evicted records of the given level, if any were evicted.
+    pub fn evicted(&self, level: LogLevel) -> Option<&EvictedRecords> {
+        self.evicted.get(&level)
+    }
+
+    /// Text for the marker stating that older records were dropped, e.g.
+    /// "[37 older records were dropped: 30 DEBUG, 7 INFO, from 12:00:01 to 12:00:05]".
+    /// Only levels that pass `min_display_level` are included, and only records that weren't
+    /// archived. Returns `None` if there is nothing to report, or if `show_evicted_marker` is
+    /// disabled.
+    pub(crate) fn evicted_marker_text(&self) -> Option<String> {
+        if !self.show_evicted_marker {
+            return None;
+        }
+        let evicted: Vec<(LogLevel, &EvictedRecords)> =
+            [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug]
+                .into_iter()
+                .filter(|level| *level >= self.min_display_level)
+                .filter_map(|level| self.dropped.get(&level).map(|e| (level, e)))
+                .collect();
+        if evicted.is_empty() {
+            return None;
+        }
+
+        let total: u64 = evicted.iter().map(|(_, e)| e.count).sum();
+        let per_level = evicted
+            .iter()
+            .map(|(level, e)| format!("{} {}", e.count, level.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let oldest = evicted.iter().map(|(_, e)| e.oldest).min().unwrap();
+        let newest = evicted.iter().map(|(_, e)| e.newest).max().unwrap();
+        let time_range = if self.time_format == TimeFormat::Hide {
+            String::new()
+        } else {
+            format!(
+                ", from {} to {}",
+                self.format_time(oldest).trim_end(),
+                self.format_time(newest).trim_end()
+            )
+        };
+        let noun = if total == 1 { "record was" } else { "records were" };
+        Some(format!("[{total} older {noun} dropped: {per_level}{time_range}]"))
+    }
+
     pub(crate) fn get_time_format_padding(&self) -> usize {
         // Format a time and use that to determine the padding to provide for all other rows.
         // TODO: Does this work? It feels like using a fixed timestamp would be better than
         //  non-determinism. Maybe I can get this value at compile-time, or find it myself
         //  and hard-code it in.
-        self.format_time(Local::now()).len()
+        match self.time_format {
+            // Relative times have a varying length, so pad to one of the longer ones.
+            TimeFormat::Relative => "59m ago ".len(),
+            _ => self.format_time(self.now().fixed_offset()).len(),
+        }
     }
 
     pub fn show_category<T: ToString>(&mut self, category: T) {
@@ -337,16 +1448,89 @@ impl EguiLogger {
         self.hidden_categories.insert(category.to_string());
     }
 
-    pub(crate) fn format_time(&self, time: chrono::DateTime<chrono::Local>) -> String {
+    /// Formats the timestamp of a record, along with its app time. Unlike `format_time`, this
+    /// supports `TimeFormat::Delta`.
+    pub(crate) fn format_record_time(&self, record: &LogRecord) -> String {
+        let time = self.format_wall_time(record);
+        match (&record.app_time, self.app_time_display) {
+            (Some(app_time), AppTimeDisplay::Alongside) => format!("{}{} ", time, app_time),
+            (Some(app_time), AppTimeDisplay::Instead) => format!("{} ", app_time),
+            _ => time,
+        }
+    }
+
+    fn format_wall_time(&self, record: &LogRecord) -> String {
+        if self.time_format != TimeFormat::Delta {
+            return self.format_time(record.timestamp);
+        }
+        let delta = self
+            .previous_timestamp(record)
+            .map_or(TimeDelta::zero(), |previous| record.timestamp - previous);
+        let millis = delta.num_milliseconds();
+        match self.time_precision {
+            TimePrecision::Seconds => format!("+{}s ", millis / 1000),
+            TimePrecision::Milliseconds => format!("+{}.{:03}s ", millis / 1000, millis % 1000),
+        }
+    }
+
+    /// Timestamp of the stored record just before this one.
+    fn previous_timestamp(&self, record: &LogRecord) -> Option<DateTime<FixedOffset>> {
+        let before =
+            |r: &LogRecord| r.timestamp < record.timestamp || (r.timestamp == record.timestamp && r.id < record.id);
+        // Records of each level are stored in the order they're logged, which is by timestamp unless
+        // `log_record` was given records out of order. Deltas are only approximate in that case.
+        let stored = self.records.values().filter_map(|records| {
+            let i = records.partition_point(before);
+            i.checked_sub(1).map(|i| records[i].timestamp)
+        });
+        let archived = self
+            .archive
+            .iter()
+            .flat_map(|a| a.loaded())
+            .filter(|r| before(r))
+            .map(|r| r.timestamp);
+        stored.chain(archived).max()
+    }
+
+    /// Whether `custom_time_format` is a valid strftime pattern.
+    pub(crate) fn custom_time_format_is_valid(&self) -> bool {
+        !StrftimeItems::new(&self.custom_time_format).any(|item| matches!(item, Item::Error))
+    }
+
+    /// Converts a time to `time_zone`.
+    fn in_display_zone(&self, time: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+        match self.time_zone {
+            DisplayZone::Local => time.with_timezone(&Local).fixed_offset(),
+            DisplayZone::Utc => time.to_utc().fixed_offset(),
+            DisplayZone::Original => time,
+            DisplayZone::Fixed(offset) => FixedOffset::east_opt(offset).map_or(time, |zone| time.with_timezone(&zone)),
+        }
+    }
+
+    /// Date and time in `time_zone`, regardless of `time_format`.
+    pub(crate) fn format_zoned_time(&self, time: DateTime<FixedOffset>) -> String {
+        self.in_display_zone(time).format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+
+    pub(crate) fn format_time(&self, time: DateTime<FixedOffset>) -> String {
+        let zoned = self.in_display_zone(time);
         let time = match (self.time_format, self.time_precision) {
+            (TimeFormat::SinceStart | TimeFormat::Delta, precision) => {
+                format_elapsed(time - self.start_time.fixed_offset(), precision)
+            }
+            (TimeFormat::Relative, _) => format_ago(self.now().fixed_offset() - time),
+            (TimeFormat::Custom, _) if self.custom_time_format_is_valid() => zoned
+                .format_with_items(StrftimeItems::new(&self.custom_time_format))
+                .to_string(),
+            (TimeFormat::Custom, _) => zoned.format("%T").to_string(),
             (TimeFormat::Utc, TimePrecision::Seconds) => {
                 time.to_utc().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
             }
             (TimeFormat::Utc, TimePrecision::Milliseconds) => {
                 time.to_utc().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
             }
-            (TimeFormat::LocalTime, TimePrecision::Seconds) => time.format("%T").to_string(),
-            (TimeFormat::LocalTime, TimePrecision::Milliseconds) => time.format("%T%.3f").to_string(),
+            (TimeFormat::LocalTime, TimePrecision::Seconds) => zoned.format("%T").to_string(),
+            (TimeFormat::LocalTime, TimePrecision::Milliseconds) => zoned.format("%T%.3f").to_string(),
             (TimeFormat::Hide, _) => String::new(),
         };
         if self.time_format == TimeFormat::Hide {
@@ -357,10 +1541,6 @@ impl EguiLogger {
     }
 
     // Internal state accessors for UI
-    pub(crate) fn records(&self) -> &HashMap<LogLevel, VecDeque<LogRecord>> {
-        &self.records
-    }
-
     pub(crate) fn category_counts(&self) -> &HashMap<String, u32> {
         &self.category_counts
     }
@@ -385,14 +1565,515 @@ impl EguiLogger {
         &mut self.input_text
     }
 
+    /// Number of rows the input area can grow to before it scrolls, counting wrapped rows.
+    pub(crate) fn max_input_rows(&self) -> usize {
+        if self.multiline_input {
+            self.max_input_lines.max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Height of the input area when it was last shown. `None` until it has been shown.
+    pub(crate) fn input_area_height(&self) -> Option<f32> {
+        self.input_area_height
+    }
+
+    pub(crate) fn set_input_area_height(&mut self, height: f32) {
+        self.input_area_height = Some(height);
+    }
+
     pub(crate) fn take_input_text(&mut self) -> String {
+        self.completion = None;
         std::mem::take(&mut self.input_text)
     }
 
-    pub(crate) fn input_categories(&self) -> &[String] {
+    pub(crate) fn completion(&self) -> Option<&Completion> {
+        self.completion.as_ref()
+    }
+
+    /// Recompute the completion suggestions for the current input text.
+    pub(crate) fn update_completion(&mut self) {
+        if !self.enable_commands {
+            self.completion = None;
+            return;
+        }
+        let builtins: Vec<&str> = self.builtin_commands().map(|(name, _, _)| name).collect();
+        let builtin_args = |name: &str, index: usize| match (name, index) {
+            ("get" | "set" | "reset", 0) => self.cvars.keys().cloned().collect(),
+            ("set", 1) => Vec::new(),
+            ("help", 0) => self
+                .commands
+                .iter()
+                .map(|c| c.name())
+                .chain(builtins.iter().copied())
+                .map(|n| n.to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.completion = self.commands.completions(&self.input_text, &builtins, builtin_args);
+    }
+
+    /// Apply the next (or previous) completion suggestion to the input text.
+    /// Returns false if there is nothing to complete.
+    pub(crate) fn cycle_completion(&mut self, forward: bool) -> bool {
+        if self.completion.is_none() {
+            self.update_completion();
+        }
+        let Some(completion) = &mut self.completion else {
+            return false;
+        };
+        self.input_text = completion.cycle(&self.input_text, forward);
+        true
+    }
+
+    /// Apply the completion suggestion at `index` to the input text.
+    pub(crate) fn select_completion(&mut self, index: usize) {
+        if let Some(completion) = &mut self.completion {
+            self.input_text = completion.select(&self.input_text, index);
+        }
+    }
+
+    /// Hide the completion suggestions, keeping the input text as is.
+    pub(crate) fn close_completion(&mut self) {
+        self.completion = None;
+    }
+
+    pub fn input_categories(&self) -> &[String] {
         &self.input_categories
     }
 
+    /// Only show records logged at or after `start`. The end of a [`TimeRange::Between`] range is
+    /// kept if it's not before `start`.
+    pub fn show_from(&mut self, start: DateTime<FixedOffset>) {
+        let end = match self.time_range {
+            Some(TimeRange::Between { end, .. }) => end.filter(|end| *end >= start),
+            _ => None,
+        };
+        self.time_range = Some(TimeRange::Between {
+            start: Some(start),
+            end,
+        });
+    }
+
+    /// Only show records logged at or before `end`. The start of a [`TimeRange::Between`] range is
+    /// kept if it's not after `end`.
+    pub fn show_until(&mut self, end: DateTime<FixedOffset>) {
+        let start = match self.time_range {
+            Some(TimeRange::Between { start, .. }) => start.filter(|start| *start <= end),
+            _ => None,
+        };
+        self.time_range = Some(TimeRange::Between { start, end: Some(end) });
+    }
+
+    /// Short description of `time_range`, e.g. "Last 5m". `None` when there's no range.
+    pub(crate) fn time_range_text(&self) -> Option<String> {
+        let bound = |time| self.format_zoned_time(time);
+        let text = match self.time_range? {
+            TimeRange::Last(duration) => format!("Last {}", format_span(duration.as_secs() as i64)),
+            TimeRange::Between { start, end } => match (start, end) {
+                (Some(start), Some(end)) => format!("{} to {}", bound(start), bound(end)),
+                (Some(start), None) => format!("From {}", bound(start)),
+                (None, Some(end)) => format!("Until {}", bound(end)),
+                (None, None) => return None,
+            },
+        };
+        Some(text)
+    }
+
+    /// Freeze the displayed records, so a burst of logs can be read. New records are still stored,
+    /// and are shown after [`Self::resume`]. Filters still apply to the frozen records.
+    pub fn pause(&mut self) {
+        if self.paused_records.is_none() {
+            self.paused_records = Some(self.sorted_records().into_iter().cloned().collect());
+            self.paused_at_id = self.next_record_id;
+        }
+    }
+
+    /// Show the stored records again, including those that arrived while paused.
+    pub fn resume(&mut self) {
+        self.paused_records = None;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_records.is_some()
+    }
+
+    /// Number of records stored since the view was paused. 0 if not paused.
+    pub fn arrived_while_paused(&self) -> u64 {
+        match self.paused_records {
+            Some(_) => self.next_record_id - self.paused_at_id,
+            None => 0,
+        }
+    }
+
+    /// Select only the record with this id.
+    pub fn select_record(&mut self, id: u64) {
+        self.selected_ids.clear();
+        self.selected_ids.insert(id);
+        self.selection_anchor = Some(id);
+        self.selection_cursor = Some(id);
+    }
+
+    /// Add the record with this id to the selection, or remove it if it's already selected.
+    pub fn toggle_record_selected(&mut self, id: u64) {
+        if !self.selected_ids.remove(&id) {
+            self.selected_ids.insert(id);
+        }
+        self.selection_anchor = Some(id);
+        self.selection_cursor = Some(id);
+    }
+
+    /// Select the displayed records from the last selected record up to the one with this id,
+    /// replacing the current selection.
+    pub fn select_record_range(&mut self, id: u64) {
+        let ids = self.displayed_ids();
+        let position = |id| ids.iter().position(|i| *i == id);
+        let (Some(anchor), Some(end)) = (self.selection_anchor.and_then(position), position(id)) else {
+            self.select_record(id);
+            return;
+        };
+        self.selected_ids = ids[anchor.min(end)..=anchor.max(end)].iter().copied().collect();
+        self.selection_cursor = Some(id);
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_ids.clear();
+        self.selection_anchor = None;
+        self.selection_cursor = None;
+    }
+
+    pub fn is_record_selected(&self, id: u64) -> bool {
+        self.selected_ids.contains(&id)
+    }
+
+    /// Selected records that match the current filters, sorted by timestamp.
+    pub fn selected_records(&self) -> Vec<&LogRecord> {
+        self.filtered_records()
+            .into_iter()
+            .filter(|r| self.selected_ids.contains(&r.id))
+            .collect()
+    }
+
+    /// The selected records as text, one per line, as they're shown in the log area.
+    pub fn selected_text(&self) -> String {
+        self.selected_records()
+            .into_iter()
+            .map(|r| self.format_record_text(r) + "\n")
+            .collect()
+    }
+
+    /// Select the previous or next displayed record. With `extend`, the selection grows from the
+    /// record it was started at instead.
+    pub(crate) fn move_selection(&mut self, forward: bool, extend: bool) {
+        let ids = self.displayed_ids();
+        if ids.is_empty() {
+            return;
+        }
+        let current = self.selection_cursor.and_then(|id| ids.iter().position(|i| *i == id));
+        let next = match current {
+            Some(i) if forward => (i + 1).min(ids.len() - 1),
+            Some(i) => i.saturating_sub(1),
+            // Start from the newest record, which is where the view usually is.
+            None => ids.len() - 1,
+        };
+        if extend {
+            self.select_record_range(ids[next]);
+        } else {
+            self.select_record(ids[next]);
+        }
+        self.scroll_to_selection = true;
+    }
+
+    pub(crate) fn has_selection(&self) -> bool {
+        !self.selected_ids.is_empty()
+    }
+
+    pub(crate) fn selection_cursor(&self) -> Option<u64> {
+        self.selection_cursor
+    }
+
+    pub(crate) fn take_scroll_to_selection(&mut self) -> bool {
+        std::mem::take(&mut self.scroll_to_selection)
+    }
+
+    /// The most recently selected record, whose details are shown in the detail pane.
+    pub fn detail_record(&self) -> Option<&LogRecord> {
+        let id = self.selection_cursor.filter(|id| self.selected_ids.contains(id))?;
+        self.sorted_records().into_iter().find(|r| r.id == id)
+    }
+
+    /// Fields shown in the detail pane for a record, as name and value.
+    pub(crate) fn record_details(&self, record: &LogRecord) -> Vec<(&'static str, String)> {
+        let full_time = |time: DateTime<FixedOffset>| time.format("%Y-%m-%d %H:%M:%S%.3f %:z").to_string();
+        let mut details = vec![
+            (
+                "Local time",
+                full_time(record.timestamp.with_timezone(&Local).fixed_offset()),
+            ),
+            ("Original time", full_time(record.timestamp)),
+            (
+                "UTC",
+                record
+                    .timestamp
+                    .to_utc()
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            ),
+            ("Level", record.level.as_str().to_string()),
+            ("Categories", record.categories.join(", ")),
+            ("Id", record.id.to_string()),
+        ];
+        if let Some(app_time) = &record.app_time {
+            details.push(("App time", app_time.clone()));
+        }
+        if let Some(note) = &record.bookmark {
+            details.push(("Bookmark", note.clone()));
+        }
+        if record.repeats > 0 {
+            let last = record.latest_timestamp().with_timezone(&Local).fixed_offset();
+            details.push(("Repeats", record.repeats.to_string()));
+            details.push(("Last repeat", full_time(last)));
+        }
+        details
+    }
+
+    /// Bookmark the record with this id, with an optional note, or change the note of its
+    /// bookmark. Bookmarks are stored in the record, so they're kept when it's saved, archived or
+    /// logged into another logger. Returns false if there's no record with this id.
+    pub fn bookmark_record(&mut self, id: u64, note: impl Into<String>) -> bool {
+        let note = note.into();
+        self.update_record(id, |record| record.bookmark = Some(note.clone()))
+    }
+
+    /// Returns false if there's no record with this id.
+    pub fn remove_bookmark(&mut self, id: u64) -> bool {
+        self.update_record(id, |record| record.bookmark = None)
+    }
+
+    /// All bookmarked records, including those hidden by the filters, sorted by timestamp.
+    pub fn bookmarked_records(&self) -> Vec<&LogRecord> {
+        self.sorted_records()
+            .into_iter()
+            .filter(|r| r.bookmark.is_some())
+            .collect()
+    }
+
+    /// Pin the record with this id at the top of the log area, where it stays visible while the
+    /// rest of the log scrolls, or unpin it. Returns false if there's no record with this id.
+    pub fn set_pinned(&mut self, id: u64, pinned: bool) -> bool {
+        self.update_record(id, |record| record.pinned = pinned)
+    }
+
+    /// Pin all selected records.
+    pub fn pin_selected(&mut self) {
+        for id in self.selected_ids.clone() {
+            self.set_pinned(id, true);
+        }
+    }
+
+    pub fn unpin_all(&mut self) {
+        let pinned: Vec<u64> = self.pinned_records().iter().map(|r| r.id).collect();
+        for id in pinned {
+            self.set_pinned(id, false);
+        }
+    }
+
+    /// All pinned records, including those hidden by the filters, sorted by timestamp.
+    pub fn pinned_records(&self) -> Vec<&LogRecord> {
+        self.sorted_records().into_iter().filter(|r| r.pinned).collect()
+    }
+
+    /// Select the record with this id and scroll to it, if it's displayed.
+    pub fn jump_to_record(&mut self, id: u64) {
+        self.select_record(id);
+        self.scroll_to_selection = true;
+    }
+
+    /// Applies `change` to the record with this id, wherever it's kept: in memory, loaded back
+    /// from the archive, or in the paused view. Returns false if there's no such record.
+    fn update_record(&mut self, id: u64, change: impl Fn(&mut LogRecord)) -> bool {
+        let mut found = false;
+        // Stored records count towards the memory budget, which a bookmark note changes.
+        for record in self.records.values_mut().flatten().filter(|r| r.id == id) {
+            self.memory_usage = self.memory_usage.saturating_sub(record.approximate_size());
+            change(record);
+            self.memory_usage += record.approximate_size();
+            found = true;
+        }
+        let loaded = self.archive.iter_mut().flat_map(|a| a.loaded_mut().iter_mut());
+        let paused = self.paused_records.iter_mut().flatten();
+        for record in loaded.chain(paused).filter(|r| r.id == id) {
+            change(record);
+            found = true;
+        }
+        // A longer note may take the records over the budget.
+        self.enforce_limits();
+        found
+    }
+
+    /// Ids of the displayed records, in display order.
+    fn displayed_ids(&self) -> Vec<u64> {
+        self.displayed_records().iter().map(|r| r.id).collect()
+    }
+
+    /// Number of records matching the current filters that were logged while the log area wasn't
+    /// showing the newest records.
+    pub fn unread_count(&self) -> usize {
+        let Some(first) = self.first_unread_id else {
+            return 0;
+        };
+        self.records
+            .values()
+            .flatten()
+            .filter(|r| r.id >= first && self.matches_filters(r))
+            .count()
+    }
+
+    /// Id of the oldest unread record, see [`Self::unread_count`]. `None` if there are none.
+    pub fn first_unread_id(&self) -> Option<u64> {
+        self.first_unread_id
+    }
+
+    /// Whether a record was stored since the user scrolled away from the bottom, or stopped
+    /// following new records. Records loaded back from the archive are never unread.
+    pub(crate) fn is_unread(&self, record: &LogRecord) -> bool {
+        self.first_unread_id.is_some_and(|first| record.id >= first)
+            && !self.archive.as_ref().is_some_and(|a| a.is_loaded(record.id))
+    }
+
+    /// Scroll the log area to the newest records in the next frame, which marks them as read.
+    pub fn scroll_to_newest(&mut self) {
+        self.scroll_to_newest = true;
+    }
+
+    pub(crate) fn take_scroll_to_newest(&mut self) -> bool {
+        std::mem::take(&mut self.scroll_to_newest)
+    }
+
+    /// Called by the UI with the scroll position of the log area. Reaching the bottom marks all
+    /// records as read.
+    pub(crate) fn set_scrolled_to_bottom(&mut self, at_bottom: bool) {
+        self.scrolled_to_bottom = at_bottom;
+        if at_bottom {
+            self.first_unread_id = None;
+        }
+    }
+
+    pub(crate) fn scrolled_to_bottom(&self) -> bool {
+        self.scrolled_to_bottom
+    }
+
+    /// Show the log as tabs, each with its own filters. The first tab becomes active, and its
+    /// filters replace the current ones. An empty list removes the tab bar.
+    pub fn set_tabs(&mut self, tabs: Vec<LogTab>) {
+        self.tabs = tabs;
+        self.active_tab = 0;
+        self.load_active_tab();
+    }
+
+    /// Add a tab, returning its index. If it's the first tab, it becomes active.
+    pub fn add_tab(&mut self, tab: LogTab) -> usize {
+        self.tabs.push(tab);
+        if self.tabs.len() == 1 {
+            self.active_tab = 0;
+            self.load_active_tab();
+        }
+        self.tabs.len() - 1
+    }
+
+    /// Remove the tab at `index`. If it was active, the next tab becomes active.
+    pub fn remove_tab(&mut self, index: usize) -> Option<LogTab> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+        self.save_active_tab();
+        let tab = self.tabs.remove(index);
+        if index < self.active_tab {
+            self.active_tab -= 1;
+        } else if index == self.active_tab {
+            self.active_tab = self.active_tab.min(self.tabs.len().saturating_sub(1));
+            self.load_active_tab();
+        }
+        Some(tab)
+    }
+
+    /// The tabs, in display order. The active tab's filters are copied from the logger's own filter
+    /// settings every frame, and when switching tabs.
+    pub fn tabs(&self) -> &[LogTab] {
+        &self.tabs
+    }
+
+    /// Index of the active tab, or `None` if there are no tabs.
+    pub fn active_tab(&self) -> Option<usize> {
+        (!self.tabs.is_empty()).then_some(self.active_tab)
+    }
+
+    /// Switch to the tab at `index`, replacing the current filters with its filters and picking its
+    /// input channel. Returns false if there is no such tab.
+    pub fn select_tab(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        self.save_active_tab();
+        self.active_tab = index;
+        self.load_active_tab();
+        true
+    }
+
+    pub(crate) fn tab_mut(&mut self, index: usize) -> Option<&mut LogTab> {
+        self.tabs.get_mut(index)
+    }
+
+    /// Add a tab with the current filters and switch to it. If there are no tabs yet, the current
+    /// filters become the first tab as well.
+    pub(crate) fn add_tab_from_filters(&mut self) {
+        if self.tabs.is_empty() {
+            self.tabs.push(LogTab::new("Tab 1"));
+            self.active_tab = 0;
+        }
+        self.save_active_tab();
+        let mut tab = self.tabs[self.active_tab].clone();
+        tab.name = format!("Tab {}", self.tabs.len() + 1);
+        tab.input_channel = self.selected_input_channel.clone();
+        self.tabs.push(tab);
+        self.active_tab = self.tabs.len() - 1;
+    }
+
+    /// Copy the filter settings into the active tab.
+    pub(crate) fn save_active_tab(&mut self) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        let search_changed = tab.search_term != self.search_term
+            || tab.search_with_regex != self.search_with_regex
+            || tab.search_with_case_sensitive != self.search_with_case_sensitive;
+        tab.min_display_level = self.min_display_level;
+        tab.hidden_categories.clone_from(&self.hidden_categories);
+        tab.search_term.clone_from(&self.search_term);
+        tab.search_with_regex = self.search_with_regex;
+        tab.search_with_case_sensitive = self.search_with_case_sensitive;
+        if search_changed {
+            tab.update_search_regex();
+        }
+    }
+
+    /// Replace the filter settings with those of the active tab.
+    fn load_active_tab(&mut self) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        tab.mark_read();
+        self.min_display_level = tab.min_display_level;
+        self.hidden_categories.clone_from(&tab.hidden_categories);
+        self.search_term.clone_from(&tab.search_term);
+        self.search_with_regex = tab.search_with_regex;
+        self.search_with_case_sensitive = tab.search_with_case_sensitive;
+        if let Some(channel) = tab.input_channel.clone() {
+            self.select_input_channel(&channel);
+        }
+        self.update_search_regex();
+    }
+
     pub(crate) fn update_search_regex(&mut self) {
         if self.search_with_regex {
             self.search_regex = RegexBuilder::new(&self.search_term)
@@ -406,3 +2087,41 @@ impl EguiLogger {
         &mut self.hidden_categories
     }
 }
+
+fn unknown_cvar(name: &str) -> String {
+    format!("Unknown console variable '{}'. Type /list to see them all.", name)
+}
+
+/// Formats a duration as hours, minutes and seconds, e.g. "1:02:03" or "-0:00:05.250".
+fn format_elapsed(elapsed: TimeDelta, precision: TimePrecision) -> String {
+    let sign = if elapsed < TimeDelta::zero() { "-" } else { "" };
+    let elapsed = elapsed.abs();
+    let seconds = elapsed.num_seconds();
+    let time = format!(
+        "{}{}:{:02}:{:02}",
+        sign,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    match precision {
+        TimePrecision::Seconds => time,
+        TimePrecision::Milliseconds => format!("{}.{:03}", time, elapsed.num_milliseconds() % 1000),
+    }
+}
+
+/// Formats how long ago something happened in its largest unit, e.g. "12s ago" or "3h ago".
+fn format_ago(ago: TimeDelta) -> String {
+    format!("{} ago", format_span(ago.num_seconds()))
+}
+
+/// Formats a number of seconds in its largest whole unit, e.g. "5m".
+fn format_span(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86400 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
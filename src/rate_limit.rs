@@ -0,0 +1,94 @@
+use chrono::{DateTime, FixedOffset, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::types::LogLevel;
+
+/// Limits how many records per second are stored for a category or log level.
+/// Records beyond the limit are suppressed, except for every `sample_one_in`-th one.
+/// When the rate drops back under the limit, a summary record is logged stating how many
+/// records were suppressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Maximum number of records stored per second before suppression starts.
+    pub max_per_second: u32,
+    /// While suppressing, store one of every `sample_one_in` records. 0 suppresses all of them.
+    pub sample_one_in: u32,
+}
+
+impl RateLimit {
+    pub fn new(max_per_second: u32) -> Self {
+        Self {
+            max_per_second,
+            sample_one_in: 0,
+        }
+    }
+
+    pub fn with_sampling(mut self, sample_one_in: u32) -> Self {
+        self.sample_one_in = sample_one_in;
+        self
+    }
+}
+
+/// Counters for a single rate limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitStats {
+    /// Records suppressed since suppression started. 0 if not currently suppressing.
+    pub suppressed: u64,
+    /// Records suppressed since the logger was created or cleared.
+    pub total_suppressed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum RateLimitKey {
+    Category(String),
+    Level(LogLevel),
+}
+
+/// Tracks the records seen in fixed one second windows for a single rate limit.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RateLimiter {
+    window_start: Option<DateTime<FixedOffset>>,
+    // Records seen in the current window, whether they were stored or not.
+    window_count: u32,
+    // Records seen while over the limit. Used for sampling.
+    over_limit_count: u64,
+    pub(crate) stats: RateLimitStats,
+}
+
+impl RateLimiter {
+    /// Starts a new window if the current one has expired. Returns the number of suppressed
+    /// records if this ended a period of suppression.
+    pub(crate) fn roll_window(&mut self, limit: &RateLimit, now: DateTime<FixedOffset>) -> Option<u64> {
+        let start = self.window_start.get_or_insert(now);
+        let elapsed = now - *start;
+        if elapsed < TimeDelta::seconds(1) {
+            return None;
+        }
+        // Suppression has ended once a full window stays within the limit.
+        let ended = self.stats.suppressed > 0
+            && (self.window_count <= limit.max_per_second || elapsed >= TimeDelta::seconds(2));
+        self.window_start = Some(now);
+        self.window_count = 0;
+        if ended {
+            self.over_limit_count = 0;
+            Some(std::mem::take(&mut self.stats.suppressed))
+        } else {
+            None
+        }
+    }
+
+    /// Counts a record in the current window, and returns whether it should be stored.
+    pub(crate) fn admit(&mut self, limit: &RateLimit) -> bool {
+        self.window_count = self.window_count.saturating_add(1);
+        if self.window_count <= limit.max_per_second {
+            return true;
+        }
+        self.over_limit_count += 1;
+        if limit.sample_one_in > 0 && self.over_limit_count.is_multiple_of(limit.sample_one_in as u64) {
+            return true;
+        }
+        self.stats.suppressed += 1;
+        self.stats.total_suppressed += 1;
+        false
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 
 use crate::types::LogLevel;
@@ -14,8 +14,85 @@ use crate::types::LogLevel;
 ///   which does the T->String conversion for you.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct LogRecord {
-    pub timestamp: DateTime<Local>,
+    /// When the record was logged, with the offset of the zone it was logged in. Records saved
+    /// with a local timestamp by older versions load with the offset they were saved with.
+    pub timestamp: DateTime<FixedOffset>,
     pub level: LogLevel,
     pub categories: Vec<String>,
     pub message: String,
+    /// Number of identical records that were merged into this one. See [`crate::RepeatCollapse`].
+    #[serde(default)]
+    pub repeats: u32,
+    /// Timestamp of the most recent merged repeat. `None` if there are no repeats.
+    #[serde(default)]
+    pub last_timestamp: Option<DateTime<FixedOffset>>,
+    /// Assigned by the logger when the record is stored, increasing in the order records are
+    /// stored. Any value set before logging the record is replaced.
+    #[serde(default)]
+    pub id: u64,
+    /// The app's own timestamp, e.g. a frame number, if the logger has an app time source.
+    /// See [`crate::EguiLogger::set_app_time`].
+    #[serde(default)]
+    pub app_time: Option<String>,
+    /// The note of the record's bookmark, which may be empty. `None` if the record isn't
+    /// bookmarked. See [`crate::EguiLogger::bookmark_record`].
+    #[serde(default)]
+    pub bookmark: Option<String>,
+    /// Whether the record is pinned at the top of the log area. See [`crate::EguiLogger::set_pinned`].
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Summary of the records of a single log level that have been evicted from the logger to stay
+/// within its limits. The records themselves are gone, but this lets the UI show that the
+/// history is incomplete.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EvictedRecords {
+    /// Number of records dropped.
+    pub count: u64,
+    /// Timestamp of the oldest dropped record.
+    pub oldest: DateTime<FixedOffset>,
+    /// Timestamp of the most recent dropped record.
+    pub newest: DateTime<FixedOffset>,
+}
+
+impl EvictedRecords {
+    pub(crate) fn new(record: &LogRecord) -> Self {
+        Self {
+            count: 1,
+            oldest: record.timestamp,
+            newest: record.timestamp,
+        }
+    }
+
+    pub(crate) fn add(&mut self, record: &LogRecord) {
+        self.count += 1;
+        self.oldest = self.oldest.min(record.timestamp);
+        self.newest = self.newest.max(record.timestamp);
+    }
+}
+
+impl LogRecord {
+    /// Whether `other` is a repeat of this record, ignoring timestamps.
+    pub(crate) fn is_repeat_of(&self, other: &LogRecord) -> bool {
+        self.level == other.level && self.categories == other.categories && self.message == other.message
+    }
+
+    /// Timestamp of the most recent occurrence of this record, including repeats.
+    pub fn latest_timestamp(&self) -> DateTime<FixedOffset> {
+        self.last_timestamp.unwrap_or(self.timestamp)
+    }
+
+    /// Approximate number of bytes this record occupies, including its heap allocations.
+    pub(crate) fn approximate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.message.capacity()
+            + self
+                .categories
+                .iter()
+                .map(|c| std::mem::size_of::<String>() + c.capacity())
+                .sum::<usize>()
+            + self.app_time.as_ref().map_or(0, String::capacity)
+            + self.bookmark.as_ref().map_or(0, String::capacity)
+    }
 }
@@ -0,0 +1,160 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::categories::IntoCategories;
+use crate::record::LogRecord;
+use crate::types::LogLevel;
+
+/// A named, filtered view of the log, shown as a tab above the log area, e.g. "All", "Combat" or
+/// "Party". All tabs show the same records, each through its own filters.
+///
+/// The filters of the active tab are the logger's own filter settings (`min_display_level`,
+/// `search_term`, hidden categories, ...), so they're edited with the usual Filter menu and
+/// search bar. They're copied back into the tab when switching to another tab.
+///
+/// ```
+/// # use egui_logger::{EguiLogger, LogLevel, LogTab};
+/// let mut logger = EguiLogger::new();
+/// logger.set_tabs(vec![
+///     LogTab::new("All"),
+///     LogTab::new("Combat").categories(["Combat"]),
+///     LogTab::new("Problems").min_level(LogLevel::Warn),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogTab {
+    pub name: String,
+    /// Only show records with at least one of these categories. Empty shows all categories.
+    pub categories: Vec<String>,
+    pub min_display_level: LogLevel,
+    /// Categories that should be hidden. New categories are shown by default.
+    pub hidden_categories: HashSet<String>,
+    pub search_term: String,
+    pub search_with_regex: bool,
+    pub search_with_case_sensitive: bool,
+    /// Name of the input channel to pick when switching to this tab. See
+    /// [`crate::EguiLogger::set_input_channels`].
+    pub input_channel: Option<String>,
+
+    // Records matching this tab's filters that were logged while another tab was active.
+    #[serde(skip)]
+    unread: usize,
+    // Compiled from `search_term` when the tab is saved, or when first counting unread records. An
+    // invalid term is kept as the error, so it isn't compiled again for every record. The active
+    // tab uses the logger's regex.
+    #[serde(skip)]
+    search_regex: Option<Result<Regex, regex::Error>>,
+}
+
+impl LogTab {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            categories: Vec::new(),
+            min_display_level: LogLevel::Debug,
+            hidden_categories: HashSet::new(),
+            search_term: String::new(),
+            search_with_regex: false,
+            search_with_case_sensitive: false,
+            input_channel: None,
+            unread: 0,
+            search_regex: None,
+        }
+    }
+
+    /// Only show records with at least one of these categories.
+    pub fn categories<C: IntoCategories>(mut self, categories: C) -> Self {
+        self.categories = categories.into_categories();
+        self
+    }
+
+    pub fn min_level(mut self, level: LogLevel) -> Self {
+        self.min_display_level = level;
+        self
+    }
+
+    pub fn search(mut self, term: impl Into<String>) -> Self {
+        self.search_term = term.into();
+        self
+    }
+
+    /// Pick the input channel with this name when switching to this tab.
+    pub fn input_channel(mut self, name: impl Into<String>) -> Self {
+        self.input_channel = Some(name.into());
+        self
+    }
+
+    /// Number of records matching this tab that were logged since it was last active.
+    pub fn unread(&self) -> usize {
+        self.unread
+    }
+
+    pub(crate) fn mark_read(&mut self) {
+        self.unread = 0;
+    }
+
+    /// Whether a record passes the `categories` filter, which unlike the other filters is not
+    /// one of the logger's own settings.
+    pub(crate) fn includes_categories(&self, record: &LogRecord) -> bool {
+        self.categories.is_empty() || record.categories.iter().any(|c| self.categories.contains(c))
+    }
+
+    /// Counts the record as unread if it matches this tab's filters. `text` is the record as it
+    /// is searched, which is only used when there is a search term.
+    pub(crate) fn count_unread(&mut self, record: &LogRecord, text: &str) {
+        if record.level < self.min_display_level
+            || !self.includes_categories(record)
+            || record.categories.iter().any(|c| self.hidden_categories.contains(c))
+        {
+            return;
+        }
+        if !self.search_term.is_empty() {
+            if self.search_with_regex && self.search_regex.is_none() {
+                self.update_search_regex();
+            }
+            let regex = self.search_regex.as_ref().and_then(|r| r.as_ref().ok());
+            if !search_matches(
+                text,
+                &self.search_term,
+                regex,
+                self.search_with_regex,
+                self.search_with_case_sensitive,
+            ) {
+                return;
+            }
+        }
+        self.unread += 1;
+    }
+
+    /// Compile the search regex, after the search settings were changed.
+    pub(crate) fn update_search_regex(&mut self) {
+        self.search_regex = self.search_with_regex.then(|| {
+            RegexBuilder::new(&self.search_term)
+                .case_insensitive(!self.search_with_case_sensitive)
+                .build()
+        });
+    }
+}
+
+/// Whether `text` matches the search term. An invalid regex matches everything.
+pub(crate) fn search_matches(
+    text: &str,
+    term: &str,
+    regex: Option<&Regex>,
+    with_regex: bool,
+    case_sensitive: bool,
+) -> bool {
+    if with_regex {
+        // Note that the regex itself is generated to be case sensitive or not, so
+        // that the regex + case check doesn't need to happen here.
+        match regex {
+            Some(regex) => regex.is_match(text),
+            None => true,
+        }
+    } else if case_sensitive {
+        text.contains(term)
+    } else {
+        text.to_lowercase().contains(&term.to_lowercase())
+    }
+}
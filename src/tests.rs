@@ -1,6 +1,10 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
-    use crate::{EguiLogger, LogLevel, TimeFormat, TimePrecision};
+    use crate::{
+        AppTimeDisplay, Command, Cvar, CvarValue, DisplayZone, EguiLogger, InputChannel, LogColumn, LogLayout,
+        LogLevel, LogRecord, LogTab, ManualClock, RateLimit, RepeatCollapse, TimeFormat, TimePrecision, TimeRange,
+    };
     use std::fmt;
     use std::fmt::Formatter;
 
@@ -414,8 +418,8 @@ mod tests {
         logger.log(LogLevel::Info, vec![LogCategory::Combat], "Combat msg");
 
         // Enable only Dialogue category
-        logger.show_category(&LogCategory::Dialogue);
-        logger.hide_category(&LogCategory::Combat);
+        logger.show_category(LogCategory::Dialogue);
+        logger.hide_category(LogCategory::Combat);
         let visible = logger.filtered_records();
         assert_eq!(visible.len(), 1);
         assert_eq!(visible[0].categories[0], "Dialogue");
@@ -449,4 +453,1263 @@ mod tests {
         assert!(records[0].message.len() <= 10);
         assert!(records[0].message.ends_with("..."));
     }
+
+    #[test]
+    fn test_max_records_per_level_eviction() {
+        let mut logger = EguiLogger::new();
+        logger.max_records_per_level = 2;
+
+        logger.log(LogLevel::Info, vec![LogCategory::Combat], "Info 1");
+        logger.log(LogLevel::Info, vec![LogCategory::Dialogue], "Info 2");
+        logger.log(LogLevel::Info, vec![LogCategory::Dialogue], "Info 3");
+        logger.log(LogLevel::Error, vec![LogCategory::Dialogue], "Error 1");
+
+        assert_eq!(logger.total_records(), 3);
+        assert_eq!(logger.evicted_count(LogLevel::Info), 1);
+        assert_eq!(logger.evicted_count(LogLevel::Error), 0);
+        assert_eq!(logger.total_evicted(), 1);
+        // The evicted record was the only one with the Combat category.
+        assert!(logger.filtered_records().iter().all(|r| r.message != "Info 1"));
+
+        logger.clear();
+        assert_eq!(logger.total_evicted(), 0);
+        assert_eq!(logger.memory_usage(), 0);
+    }
+
+    #[test]
+    fn test_memory_budget_evicts_lowest_level_first() {
+        let mut logger = EguiLogger::new();
+        logger.log(LogLevel::Debug, vec![LogCategory::Network], "Debug msg");
+        let record_size = logger.memory_usage();
+        assert!(record_size > 0);
+
+        // Room for roughly three records.
+        logger.max_total_bytes = Some(record_size * 3 + record_size / 2);
+        logger.log(LogLevel::Error, vec![LogCategory::Network], "Error msg");
+        logger.log(LogLevel::Debug, vec![LogCategory::Network], "Debug msg");
+        logger.log(LogLevel::Info, vec![LogCategory::Network], "Info msg1");
+        assert!(logger.memory_usage() <= logger.max_total_bytes.unwrap());
+
+        // Both Debug records should be dropped before any Info or Error record.
+        logger.log(LogLevel::Info, vec![LogCategory::Network], "Info msg2");
+        assert_eq!(logger.evicted_count(LogLevel::Debug), 2);
+        assert_eq!(logger.evicted_count(LogLevel::Info), 0);
+        assert_eq!(logger.evicted_count(LogLevel::Error), 0);
+        assert_eq!(logger.total_records(), 3);
+        assert!(logger.memory_usage() <= logger.max_total_bytes.unwrap());
+
+        // Bookmark notes count towards the budget too.
+        let id = logger.filtered_records()[0].id;
+        logger.bookmark_record(id, "x".repeat(record_size));
+        assert!(logger.memory_usage() <= logger.max_total_bytes.unwrap());
+        assert_eq!(logger.total_records(), 2);
+    }
+
+    #[test]
+    fn test_evicted_marker_text() {
+        let mut logger = EguiLogger::new();
+        logger.max_records_per_level = 1;
+        assert_eq!(logger.evicted_marker_text(), None);
+
+        logger.log(LogLevel::Debug, vec![LogCategory::Network], "Debug 1");
+        logger.log(LogLevel::Debug, vec![LogCategory::Network], "Debug 2");
+        logger.log(LogLevel::Debug, vec![LogCategory::Network], "Debug 3");
+        logger.log(LogLevel::Warn, vec![LogCategory::Network], "Warn 1");
+        logger.log(LogLevel::Warn, vec![LogCategory::Network], "Warn 2");
+
+        let evicted = logger.evicted(LogLevel::Debug).unwrap();
+        assert_eq!(evicted.count, 2);
+        assert!(evicted.oldest <= evicted.newest);
+
+        logger.time_format = TimeFormat::Hide;
+        assert_eq!(
+            logger.evicted_marker_text().unwrap(),
+            "[3 older records were dropped: 1 WARN, 2 DEBUG]"
+        );
+
+        // Levels that are filtered out aren't mentioned.
+        logger.min_display_level = LogLevel::Warn;
+        assert_eq!(
+            logger.evicted_marker_text().unwrap(),
+            "[1 older record was dropped: 1 WARN]"
+        );
+
+        logger.time_format = TimeFormat::LocalTime;
+        assert!(logger.evicted_marker_text().unwrap().contains(", from "));
+
+        logger.show_evicted_marker = false;
+        assert_eq!(logger.evicted_marker_text(), None);
+    }
+
+    #[test]
+    fn test_archive_load_older() {
+        let path = std::env::temp_dir().join(format!("egui_logger_archive_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut logger = EguiLogger::new();
+        logger.max_records_per_level = 2;
+        logger.enable_archive(&path);
+        logger.archive_mut().unwrap().page_size = 2;
+        assert!(!logger.archive().unwrap().has_older());
+
+        for i in 0..5 {
+            logger.log(LogLevel::Info, vec![LogCategory::Save], format!("Message {i}"));
+        }
+        assert_eq!(logger.total_records(), 2);
+        assert!(logger.archive().unwrap().has_older());
+        // The length of the file isn't saved, so it's read again when the logger is loaded.
+        let restored: EguiLogger = serde_json::from_str(&serde_json::to_string(&logger).unwrap()).unwrap();
+        assert!(restored.archive().unwrap().has_older());
+
+        // Pages are read from newest to oldest, and merged into the view in timestamp order.
+        assert_eq!(logger.load_older_archived().unwrap(), 2);
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["Message 1", "Message 2", "Message 3", "Message 4"]);
+
+        assert_eq!(logger.load_older_archived().unwrap(), 1);
+        assert_eq!(logger.filtered_records()[0].message, "Message 0");
+        assert!(!logger.archive().unwrap().has_older());
+
+        // Records evicted while browsing the archive stay visible.
+        logger.log(LogLevel::Info, vec![LogCategory::Save], "Message 5");
+        assert_eq!(logger.filtered_records().len(), 6);
+        assert!(logger.archive().unwrap().last_error().is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_min_capture_level() {
+        let mut logger = EguiLogger::new();
+        logger.min_capture_level = LogLevel::Info;
+        assert!(!logger.is_captured(LogLevel::Debug));
+        assert!(logger.is_captured(LogLevel::Warn));
+
+        logger.log(LogLevel::Debug, vec![LogCategory::Rendering], "Dropped");
+        logger.log_debug_with(vec![LogCategory::Rendering], || -> String {
+            panic!("message should not be built")
+        });
+        logger.log_info_with(vec![LogCategory::Rendering], || format!("Frame {}", 42));
+        logger.log_record(EguiLogger::get_log_record(
+            LogLevel::Debug,
+            vec![LogCategory::Rendering],
+            "Also dropped",
+        ));
+
+        let records = logger.filtered_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].message, "Frame 42");
+
+        // Lowering the display level can't bring back records that were never captured.
+        logger.min_display_level = LogLevel::Debug;
+        assert_eq!(logger.filtered_records().len(), 1);
+    }
+
+    #[test]
+    fn test_collapse_consecutive_repeats() {
+        let mut logger = EguiLogger::new();
+        logger.collapse_repeats = RepeatCollapse::Consecutive;
+        logger.time_format = TimeFormat::Hide;
+        logger.max_records_per_level = 2;
+
+        for _ in 0..37 {
+            logger.log(LogLevel::Warn, vec![LogCategory::Network], "Packet dropped");
+        }
+        logger.log(LogLevel::Warn, vec![LogCategory::Network], "Reconnecting");
+        logger.log(LogLevel::Warn, vec![LogCategory::Network], "Packet dropped");
+
+        // The 37 merged repeats count as a single record towards the limit, and the last message
+        // isn't consecutive with them, so only the merged record was evicted.
+        assert_eq!(logger.total_evicted(), 1);
+        let records = logger.filtered_records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].message, "Reconnecting");
+        assert_eq!(records[1].repeats, 0);
+        assert_eq!(logger.evicted(LogLevel::Warn).unwrap().count, 1);
+
+        logger.clear();
+        for _ in 0..3 {
+            logger.log(LogLevel::Warn, vec![LogCategory::Network], "Packet dropped");
+        }
+        let records = logger.filtered_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].repeats, 2);
+        assert!(records[0].latest_timestamp() >= records[0].timestamp);
+        assert_eq!(
+            logger.format_record_text(records[0]),
+            "[WARN] [Network] Packet dropped [×3]"
+        );
+    }
+
+    #[test]
+    fn test_collapse_repeats_within_window() {
+        let mut logger = EguiLogger::new();
+        logger.collapse_repeats = RepeatCollapse::Within(std::time::Duration::from_secs(60));
+
+        logger.log(LogLevel::Info, vec![LogCategory::Network], "Packet dropped");
+        logger.log(LogLevel::Info, vec![LogCategory::Combat], "Hit");
+        logger.log(LogLevel::Info, vec![LogCategory::Network], "Packet dropped");
+        // Different categories aren't merged.
+        logger.log(LogLevel::Info, vec![LogCategory::Combat], "Packet dropped");
+
+        let records = logger.filtered_records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].message, "Packet dropped");
+        assert_eq!(records[0].repeats, 1);
+        assert_eq!(records[2].repeats, 0);
+    }
+
+    #[test]
+    fn test_category_rate_limit() {
+        let mut logger = EguiLogger::new();
+        logger.set_category_rate_limit(LogCategory::Rendering, Some(RateLimit::new(20).with_sampling(100)));
+        let start = chrono::Local::now().fixed_offset();
+
+        // 1000 records within the same second.
+        for i in 0..1000 {
+            let mut record = EguiLogger::get_log_record(LogLevel::Debug, vec![LogCategory::Rendering], i);
+            record.timestamp = start + chrono::TimeDelta::microseconds(i);
+            logger.log_record(record);
+        }
+        // Other categories aren't limited.
+        logger.log(LogLevel::Info, vec![LogCategory::Audio], "Not limited");
+
+        // 20 within the limit, plus 1 in 100 of the remaining 980.
+        assert_eq!(logger.total_records(), 20 + 9 + 1);
+        let stats = logger.category_rate_limit_stats(LogCategory::Rendering).unwrap();
+        assert_eq!(stats.suppressed, 971);
+        assert_eq!(stats.total_suppressed, 971);
+        assert_eq!(logger.category_rate_limit_stats(LogCategory::Audio), None);
+
+        // A quiet second ends the suppression with a summary record.
+        let mut record = EguiLogger::get_log_record(LogLevel::Debug, vec![LogCategory::Rendering], "Quiet");
+        record.timestamp = start + chrono::TimeDelta::seconds(3);
+        logger.log_record(record);
+
+        let stats = logger.category_rate_limit_stats(LogCategory::Rendering).unwrap();
+        assert_eq!(stats.suppressed, 0);
+        assert_eq!(stats.total_suppressed, 971);
+        let records = logger.filtered_records();
+        let summary = records.iter().find(|r| r.message.starts_with("Suppressed")).unwrap();
+        assert_eq!(summary.message, "Suppressed 971 messages from Rendering");
+        assert_eq!(summary.level, LogLevel::Warn);
+        assert_eq!(records.last().unwrap().message, "Quiet");
+    }
+
+    #[test]
+    fn test_level_rate_limit() {
+        let mut logger = EguiLogger::new();
+        logger.set_level_rate_limit(LogLevel::Debug, Some(RateLimit::new(2)));
+        for _ in 0..5 {
+            logger.log(LogLevel::Debug, vec![LogCategory::Unknown], "Spam");
+            logger.log(LogLevel::Info, vec![LogCategory::Unknown], "Not limited");
+        }
+        assert_eq!(
+            logger.level_rate_limit_stats(LogLevel::Debug).unwrap().total_suppressed,
+            3
+        );
+        assert_eq!(logger.total_records(), 7);
+
+        logger.set_level_rate_limit(LogLevel::Debug, None);
+        logger.log(LogLevel::Debug, vec![LogCategory::Unknown], "Spam");
+        assert_eq!(logger.level_rate_limit_stats(LogLevel::Debug), None);
+        assert_eq!(logger.total_records(), 8);
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut logger = EguiLogger::new();
+        logger.input_text_prefix = "User: ".to_string();
+        logger.register_command(
+            Command::new("teleport", "Move the player", |logger, args| {
+                let x: i32 = args.parse(0)?;
+                let y: i32 = args.parse(1)?;
+                logger.log_info(vec![LogCategory::Combat], format!("Teleported to {x}, {y}"));
+                Ok(())
+            })
+            .arg("x")
+            .arg("y"),
+        );
+        logger.register_command(
+            Command::new("say", "Say something", |logger, args| {
+                logger.log_info(vec![LogCategory::Dialogue], args.rest(0));
+                Ok(())
+            })
+            .rest_arg("message"),
+        );
+
+        logger.submit_input("/teleport 10 -5");
+        logger.submit_input("/teleport 10");
+        logger.submit_input("/teleport 10 abc");
+        logger.submit_input("/teleport 1 2 3");
+        logger.submit_input("/say \"hello   there\" world");
+        logger.submit_input("/unknown");
+        logger.submit_input("Just chatting");
+
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Teleported to 10, -5",
+                "/teleport: Missing argument <y>. Usage: /teleport <x> <y>",
+                "/teleport: Invalid value 'abc' for <y>",
+                "/teleport: Too many arguments. Usage: /teleport <x> <y>",
+                "hello   there world",
+                "/unknown: Unknown command '/unknown'. Type /help for a list of commands.",
+                "User: Just chatting",
+            ]
+        );
+        let errors = logger
+            .filtered_records()
+            .iter()
+            .filter(|r| r.level == LogLevel::Error)
+            .count();
+        assert_eq!(errors, 4);
+
+        logger.clear();
+        logger.submit_input("/help");
+        let messages: Vec<String> = logger.filtered_records().iter().map(|r| r.message.clone()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Available commands:",
+                "  /say [message...] - Say something",
+                "  /teleport <x> <y> - Move the player",
+                "  /help [command] - Show this list",
+            ]
+        );
+
+        // With commands disabled, input is treated as chat.
+        logger.clear();
+        logger.enable_commands = false;
+        logger.submit_input("/help");
+        assert_eq!(logger.filtered_records()[0].message, "User: /help");
+    }
+
+    #[test]
+    fn test_command_completion() {
+        let mut logger = EguiLogger::new();
+        let noop = |_: &mut EguiLogger, _: &crate::CommandArgs| Ok(());
+        logger.register_command(Command::new("teleport", "Move the player", noop).arg("x").arg("y"));
+        logger.register_command(
+            Command::new("give", "Give an item", noop)
+                .arg("player")
+                .arg("item")
+                .completer(|index| match index {
+                    0 => vec!["Alice".to_string(), "Bob".to_string(), "Alfred Jr".to_string()],
+                    _ => vec!["sword".to_string(), "shield".to_string()],
+                }),
+        );
+
+        // Command names, including the built-in /help.
+        *logger.input_text_mut() = "/".to_string();
+        assert!(logger.cycle_completion(true));
+        assert_eq!(logger.input_text(), "/give");
+        assert!(logger.cycle_completion(true));
+        assert_eq!(logger.input_text(), "/help");
+        assert!(logger.cycle_completion(false));
+        assert_eq!(logger.input_text(), "/give");
+
+        // Argument candidates are filtered by what has been typed, ignoring case.
+        *logger.input_text_mut() = "/give al".to_string();
+        logger.update_completion();
+        assert_eq!(logger.completion().unwrap().candidates, vec!["Alfred Jr", "Alice"]);
+        assert!(logger.cycle_completion(true));
+        assert_eq!(logger.input_text(), "/give \"Alfred Jr\"");
+        assert!(logger.cycle_completion(true));
+        assert_eq!(logger.input_text(), "/give Alice");
+
+        *logger.input_text_mut() = "/give Bob s".to_string();
+        logger.update_completion();
+        assert_eq!(logger.completion().unwrap().candidates, vec!["shield", "sword"]);
+
+        // Arguments without a completer, and non-command input, have no suggestions.
+        *logger.input_text_mut() = "/teleport 1".to_string();
+        logger.update_completion();
+        assert!(logger.completion().is_none());
+        *logger.input_text_mut() = "hello".to_string();
+        assert!(!logger.cycle_completion(true));
+        assert_eq!(logger.input_text(), "hello");
+    }
+
+    #[test]
+    fn test_input_history() {
+        let mut logger = EguiLogger::new();
+        logger.max_input_history = 3;
+        for text in ["/help", "hello", "hello", "/help teleport", "how are you"] {
+            logger.submit_input(text);
+        }
+        // Consecutive duplicates are removed, and the oldest entry is dropped.
+        let history: Vec<&str> = logger.input_history().collect();
+        assert_eq!(history, vec!["how are you", "/help teleport", "hello"]);
+
+        *logger.input_text_mut() = "h".to_string();
+        assert!(logger.history_previous(false));
+        assert_eq!(logger.input_text(), "how are you");
+        assert!(logger.history_previous(false));
+        assert!(logger.history_previous(false));
+        assert_eq!(logger.input_text(), "hello");
+        assert!(!logger.history_previous(false));
+        assert_eq!(logger.input_text(), "hello");
+
+        // Going past the newest entry restores the draft.
+        assert!(logger.history_next(false));
+        assert!(logger.history_next(false));
+        assert_eq!(logger.input_text(), "how are you");
+        assert!(logger.history_next(false));
+        assert_eq!(logger.input_text(), "h");
+        assert!(!logger.history_next(false));
+
+        // Prefix search skips entries that don't start with the draft.
+        *logger.input_text_mut() = "/".to_string();
+        assert!(logger.history_previous(true));
+        assert_eq!(logger.input_text(), "/help teleport");
+        assert!(!logger.history_previous(true));
+
+        // The history is kept when the logger is serialized.
+        let json = serde_json::to_string(&logger).unwrap();
+        let restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.input_history().count(), 3);
+    }
+
+    #[test]
+    fn test_take_submitted_inputs() {
+        let mut logger = EguiLogger::new();
+        logger.input_text_prefix = "Me: ".to_string();
+        logger.submit_input("Hello");
+        logger.submit_input("/help");
+        assert_eq!(logger.filtered_records()[0].message, "Me: Hello");
+
+        // Commands are handled by the logger, so only chat is delivered.
+        let submitted = logger.take_submitted_inputs();
+        assert_eq!(submitted.len(), 1);
+        assert_eq!(submitted[0].text, "Hello");
+        assert_eq!(submitted[0].level, LogLevel::Info);
+        assert_eq!(submitted[0].categories, vec!["Input"]);
+        assert!(logger.take_submitted_inputs().is_empty());
+
+        logger.clear();
+        logger.echo_input = false;
+        logger.input_level = LogLevel::Warn;
+        logger.submit_input("Not echoed");
+        assert_eq!(logger.total_records(), 0);
+        let submitted = logger.take_submitted_inputs();
+        assert_eq!(submitted[0].text, "Not echoed");
+        assert_eq!(submitted[0].level, LogLevel::Warn);
+    }
+
+    #[test]
+    fn test_cvars() {
+        let mut logger = EguiLogger::new();
+        logger.register_cvar(Cvar::new("r_vsync", true, "Wait for vertical sync"));
+        logger.register_cvar(Cvar::new("net_latency_sim", 0, "Simulated latency in ms").range(0.0, 1000.0));
+        logger.register_cvar(Cvar::new("player_name", "Player", "Name shown to others"));
+
+        logger.submit_input("/set r_vsync 0");
+        logger.submit_input("/set net_latency_sim 120");
+        logger.submit_input("/set net_latency_sim 5000");
+        logger.submit_input("/set net_latency_sim fast");
+        logger.submit_input("/set player_name Sir Lancelot");
+        logger.submit_input("/get r_vsync");
+        logger.submit_input("/set nope 1");
+        logger.submit_input("/reset net_latency_sim");
+
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "r_vsync = 0 (was 1)",
+                "net_latency_sim = 120 (was 0)",
+                "/set: net_latency_sim must be in the range [0, 1000]",
+                "/set: 'fast' is not a valid int",
+                "player_name = \"Sir Lancelot\" (was \"Player\")",
+                "r_vsync = 0 (bool, default 1) - Wait for vertical sync",
+                "/set: Unknown console variable 'nope'. Type /list to see them all.",
+                "net_latency_sim = 0 (was 120)",
+            ]
+        );
+        assert_eq!(logger.cvar_value("r_vsync"), Some(&CvarValue::Bool(false)));
+        assert_eq!(logger.cvar_value("player_name").unwrap().as_str(), Some("Sir Lancelot"));
+
+        assert!(logger.set_cvar("net_latency_sim", 2.5).is_err());
+        logger.register_cvar(Cvar::new("cl_fov", 90.0, "Field of view").range(60.0, 120.0));
+        assert!(logger.set_cvar("cl_fov", f64::NAN).is_err());
+        assert!(logger.set_cvar("cl_fov", f64::INFINITY).is_err());
+        assert!(logger.set_cvar("net_latency_sim", 250).is_ok());
+        assert!(logger.reset_cvar("r_vsync").is_ok());
+        // Changes made by the app are logged like those made with /set and /reset.
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages[messages.len() - 2..],
+            ["net_latency_sim = 250 (was 0)", "r_vsync = 1 (was 0)"]
+        );
+
+        // Values survive serialization, and are kept when the app registers the cvars again.
+        let json = serde_json::to_string(&logger).unwrap();
+        let mut restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        restored.register_cvar(Cvar::new("net_latency_sim", 0, "Simulated latency in ms").range(0.0, 1000.0));
+        assert_eq!(restored.cvar_value("net_latency_sim").unwrap().as_int(), Some(250));
+        // Unless the saved value is no longer valid.
+        restored.register_cvar(Cvar::new("net_latency_sim", 0, "Simulated latency in ms").range(0.0, 100.0));
+        assert_eq!(restored.cvar_value("net_latency_sim").unwrap().as_int(), Some(0));
+
+        // Completion of cvar names for the built-in commands.
+        *restored.input_text_mut() = "/set net".to_string();
+        assert!(restored.cycle_completion(true));
+        assert_eq!(restored.input_text(), "/set net_latency_sim");
+    }
+
+    #[test]
+    fn test_multiline_input() {
+        let mut logger = EguiLogger::new();
+        logger.input_text_prefix = "Me: ".to_string();
+        assert_eq!(logger.max_input_rows(), 1);
+
+        logger.multiline_input = true;
+        logger.max_input_lines = 3;
+        assert_eq!(logger.max_input_rows(), 3);
+        logger.max_input_lines = 0;
+        assert_eq!(logger.max_input_rows(), 1);
+
+        logger.submit_input("first\nsecond\n\nthird");
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["Me: first", "second", "third"]);
+        // The app gets the input as it was typed.
+        assert_eq!(logger.take_submitted_inputs()[0].text, "first\nsecond\n\nthird");
+    }
+
+    #[test]
+    fn test_input_channels() {
+        let mut logger = EguiLogger::new();
+        assert!(logger.selected_input_channel().is_none());
+        assert!(!logger.select_input_channel("Party"));
+
+        logger.set_input_channels(vec![
+            InputChannel::new("Say", vec![LogCategory::Network]),
+            InputChannel::new("Party", vec![LogCategory::Network, LogCategory::Dialogue]),
+        ]);
+        // The first channel is picked by default.
+        assert_eq!(logger.selected_input_channel(), Some("Say"));
+        assert!(logger.select_input_channel("Party"));
+        logger.input_level = LogLevel::Warn;
+        logger.submit_input("hello");
+        let record = &logger.filtered_records()[0];
+        assert_eq!(record.categories, vec!["Network", "Dialogue"]);
+        assert_eq!(record.level, LogLevel::Warn);
+
+        // The choice is remembered across a save and restore.
+        let json = serde_json::to_string(&logger).unwrap();
+        let mut restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.selected_input_channel(), Some("Party"));
+        assert_eq!(restored.input_level, LogLevel::Warn);
+
+        // And kept when the app sets the channels again, if it still exists.
+        restored.set_input_channels(vec![
+            InputChannel::new("Party", vec![LogCategory::Dialogue]),
+            InputChannel::new("Say", vec![LogCategory::Network]),
+        ]);
+        assert_eq!(restored.selected_input_channel(), Some("Party"));
+        assert_eq!(restored.input_categories(), &["Dialogue".to_string()]);
+        restored.set_input_channels(vec![InputChannel::new("Say", vec![LogCategory::Network])]);
+        assert_eq!(restored.selected_input_channel(), Some("Say"));
+
+        // Setting the categories directly deselects the channel.
+        restored.set_input_categories(vec![LogCategory::Combat]);
+        assert_eq!(restored.selected_input_channel(), None);
+        assert_eq!(restored.input_categories(), &["Combat".to_string()]);
+    }
+
+    #[test]
+    fn test_tabs() {
+        let mut logger = EguiLogger::new();
+        logger.set_input_channels(vec![
+            InputChannel::new("Say", vec![LogCategory::Dialogue]),
+            InputChannel::new("Party", vec![LogCategory::Network]),
+        ]);
+        logger.set_tabs(vec![
+            LogTab::new("All"),
+            LogTab::new("Combat").categories(vec![LogCategory::Combat]),
+            LogTab::new("Problems").min_level(LogLevel::Warn).input_channel("Party"),
+            LogTab::new("Hits").search("hit"),
+        ]);
+        assert_eq!(logger.active_tab(), Some(0));
+
+        logger.log_info(vec![LogCategory::Combat], "You hit the goblin");
+        logger.log_warn(vec![LogCategory::Dialogue], "Goblin: ouch");
+        logger.log_debug(vec![LogCategory::Combat], "Damage roll");
+        let unread: Vec<usize> = logger.tabs().iter().map(|t| t.unread()).collect();
+        assert_eq!(unread, vec![0, 2, 1, 1]);
+
+        // Each tab has its own filters over the same records.
+        assert_eq!(logger.filtered_records().len(), 3);
+        assert!(logger.select_tab(1));
+        assert_eq!(logger.tabs()[1].unread(), 0);
+        assert_eq!(logger.filtered_records().len(), 2);
+        logger.min_display_level = LogLevel::Info;
+        assert_eq!(logger.filtered_records().len(), 1);
+
+        logger.select_tab(2);
+        assert_eq!(logger.min_display_level, LogLevel::Warn);
+        assert_eq!(logger.selected_input_channel(), Some("Party"));
+        assert_eq!(logger.filtered_records().len(), 1);
+
+        // Filters changed while a tab was active are kept when switching back.
+        logger.select_tab(1);
+        assert_eq!(logger.min_display_level, LogLevel::Info);
+
+        // Tabs are saved with the logger.
+        let json = serde_json::to_string(&logger).unwrap();
+        let mut restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.tabs().len(), 4);
+        assert_eq!(restored.active_tab(), Some(1));
+        assert_eq!(restored.tabs()[3].search_term, "hit");
+
+        // Removing the active tab switches to the next one.
+        restored.remove_tab(1);
+        assert_eq!(restored.active_tab(), Some(1));
+        assert_eq!(restored.tabs()[1].name, "Problems");
+        assert_eq!(restored.min_display_level, LogLevel::Warn);
+
+        // A tab's search regex is compiled when the tab is saved. An invalid one matches everything.
+        restored.search_with_regex = true;
+        restored.search_term = "(".to_string();
+        restored.select_tab(0);
+        restored.log_warn(vec![LogCategory::Dialogue], "Goblin: ouch");
+        assert_eq!(restored.tabs()[1].unread(), 1);
+        restored.select_tab(1);
+        restored.search_term = "^Ogre".to_string();
+        restored.select_tab(0);
+        restored.log_warn(vec![LogCategory::Dialogue], "Goblin: ouch again");
+        assert_eq!(restored.tabs()[1].unread(), 0);
+    }
+
+    #[test]
+    fn test_unread_records() {
+        let mut logger = EguiLogger::new();
+        logger.log_info(vec![LogCategory::Combat], "seen");
+        assert_eq!(logger.unread_count(), 0);
+
+        // The user scrolled up to read older records.
+        logger.set_scrolled_to_bottom(false);
+        logger.log_info(vec![LogCategory::Combat], "first new");
+        logger.log_debug(vec![LogCategory::Network], "second new");
+        assert_eq!(logger.unread_count(), 2);
+        let first = logger.filtered_records()[1].id;
+        assert_eq!(logger.first_unread_id(), Some(first));
+        // Only records matching the filters count.
+        logger.min_display_level = LogLevel::Info;
+        assert_eq!(logger.unread_count(), 1);
+
+        // Scrolling back down marks them as read.
+        logger.set_scrolled_to_bottom(true);
+        assert_eq!(logger.unread_count(), 0);
+
+        // When not following, new records are unread even at the bottom.
+        logger.follow_newest = false;
+        logger.log_warn(vec![LogCategory::Combat], "paused");
+        assert_eq!(logger.unread_count(), 1);
+
+        // Ids keep increasing in the order records are stored.
+        let ids: Vec<u64> = logger.filtered_records().iter().map(|r| r.id).collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_pause_view() {
+        let mut logger = EguiLogger::new();
+        logger.log_info(vec![LogCategory::Combat], "before");
+        logger.log_debug(vec![LogCategory::Network], "before debug");
+        logger.pause();
+        assert!(logger.is_paused());
+
+        logger.log_info(vec![LogCategory::Combat], "during");
+        logger.log_info(vec![LogCategory::Combat], "during again");
+        assert_eq!(logger.arrived_while_paused(), 2);
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["before", "before debug"]);
+        // Filters still apply to the frozen records.
+        logger.min_display_level = LogLevel::Info;
+        assert_eq!(logger.filtered_records().len(), 1);
+
+        // Records evicted while paused are still shown until resuming.
+        logger.max_records_per_level = 1;
+        logger.log_info(vec![LogCategory::Combat], "latest");
+        assert_eq!(logger.filtered_records()[0].message, "before");
+
+        logger.resume();
+        assert!(!logger.is_paused());
+        assert_eq!(logger.arrived_while_paused(), 0);
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["latest"]);
+    }
+
+    #[test]
+    fn test_record_selection() {
+        let mut logger = EguiLogger::new();
+        logger.show_categories = false;
+        logger.show_level = false;
+        logger.time_format = TimeFormat::Hide;
+        for i in 0..5 {
+            logger.log_info(vec![LogCategory::Combat], format!("record {i}"));
+        }
+        let ids: Vec<u64> = logger.filtered_records().iter().map(|r| r.id).collect();
+
+        logger.select_record(ids[1]);
+        logger.select_record_range(ids[3]);
+        assert_eq!(logger.selected_text(), "record 1\nrecord 2\nrecord 3\n");
+        logger.toggle_record_selected(ids[2]);
+        logger.toggle_record_selected(ids[4]);
+        assert_eq!(logger.selected_text(), "record 1\nrecord 3\nrecord 4\n");
+
+        // Shift+click extends from the last clicked record.
+        logger.select_record_range(ids[0]);
+        assert_eq!(logger.selected_records().len(), 5);
+
+        // Up/Down move a single selection, Shift+Up/Down extend it.
+        logger.select_record(ids[2]);
+        logger.move_selection(false, false);
+        assert!(logger.is_record_selected(ids[1]) && !logger.is_record_selected(ids[2]));
+        logger.move_selection(true, true);
+        logger.move_selection(true, true);
+        assert_eq!(logger.selected_text(), "record 1\nrecord 2\nrecord 3\n");
+
+        // The selection survives new records.
+        logger.log_info(vec![LogCategory::Combat], "record 5");
+        assert_eq!(logger.selected_records().len(), 3);
+
+        logger.clear_selection();
+        assert!(logger.selected_records().is_empty());
+        // Without a selection, Up starts from the newest record.
+        logger.move_selection(false, false);
+        assert_eq!(logger.selected_text(), "record 5\n");
+    }
+
+    #[test]
+    fn test_record_details() {
+        let mut logger = EguiLogger::new();
+        logger.collapse_repeats = RepeatCollapse::Consecutive;
+        logger.log_warn(vec![LogCategory::Combat, LogCategory::Network], "Lag spike");
+        logger.log_warn(vec![LogCategory::Combat, LogCategory::Network], "Lag spike");
+        assert!(logger.detail_record().is_none());
+
+        let id = logger.filtered_records()[0].id;
+        logger.select_record(id);
+        let record = logger.detail_record().unwrap();
+        let details = logger.record_details(record);
+        let value = |name: &str| details.iter().find(|(n, _)| *n == name).map(|(_, v)| v.clone());
+        assert_eq!(value("Level").as_deref(), Some("WARN"));
+        assert_eq!(value("Categories").as_deref(), Some("Combat, Network"));
+        assert_eq!(value("Repeats").as_deref(), Some("1"));
+        assert!(value("UTC").unwrap().ends_with('Z'));
+        assert!(value("Local time").is_some());
+
+        logger.clear_selection();
+        assert!(logger.detail_record().is_none());
+    }
+
+    #[test]
+    fn test_table_layout() {
+        let mut logger = EguiLogger::new();
+        logger.log_warn(vec![LogCategory::Network], "b");
+        logger.log_error(vec![LogCategory::Combat, LogCategory::UI], "c");
+        logger.log_info(vec![LogCategory::Audio], "a");
+        let messages = |logger: &EguiLogger| -> Vec<String> {
+            logger.displayed_records().iter().map(|r| r.message.clone()).collect()
+        };
+
+        // Sorting only applies to the table layout.
+        logger.toggle_table_sort(LogColumn::Message);
+        assert_eq!(messages(&logger), vec!["b", "c", "a"]);
+        logger.layout = LogLayout::Table;
+        assert_eq!(messages(&logger), vec!["a", "b", "c"]);
+        // Clicking the same column again sorts descending, then back to chronological.
+        logger.toggle_table_sort(LogColumn::Message);
+        assert_eq!(messages(&logger), vec!["c", "b", "a"]);
+        logger.toggle_table_sort(LogColumn::Message);
+        assert_eq!(logger.sort_column, None);
+        assert_eq!(messages(&logger), vec!["b", "c", "a"]);
+        logger.toggle_table_sort(LogColumn::Level);
+        logger.toggle_table_sort(LogColumn::Level);
+        assert_eq!(messages(&logger), vec!["c", "b", "a"]);
+
+        // Shift+click ranges follow the displayed order.
+        let ids: Vec<u64> = logger.displayed_records().iter().map(|r| r.id).collect();
+        logger.select_record(ids[0]);
+        logger.select_record_range(ids[1]);
+        assert_eq!(logger.selected_records().len(), 2);
+        assert!(!logger.is_record_selected(ids[2]));
+
+        let record = logger.displayed_records()[0];
+        assert_eq!(logger.column_text(record, LogColumn::Categories), "Combat, UI");
+        assert_eq!(logger.column_text(record, LogColumn::Level), "ERROR");
+
+        // Column widths and visibility are saved with the settings.
+        logger.table_columns[1].width = 80.0;
+        logger.table_columns[2].visible = false;
+        let json = serde_json::to_string(&logger).unwrap();
+        let restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.table_columns, logger.table_columns);
+        assert_eq!(restored.sort_column, Some(LogColumn::Level));
+    }
+
+    #[test]
+    fn test_relative_time_formats() {
+        let mut logger = EguiLogger::new();
+        logger.show_categories = false;
+        logger.show_level = false;
+        logger.time_precision = TimePrecision::Milliseconds;
+        let now = chrono::Local::now().fixed_offset();
+        for (millis, message) in [(-1250, "first"), (-1000, "second"), (0, "third")] {
+            let mut record = EguiLogger::get_log_record(LogLevel::Info, vec![LogCategory::Combat], message);
+            record.timestamp = now + chrono::TimeDelta::milliseconds(millis);
+            logger.log_record(record);
+        }
+        let times = |logger: &EguiLogger| -> Vec<String> {
+            let records = logger.filtered_records();
+            records.iter().map(|r| logger.format_record_time(r)).collect()
+        };
+
+        // The time since the previous record, which is also what search and copy see.
+        logger.time_format = TimeFormat::Delta;
+        assert_eq!(times(&logger), vec!["+0.000s ", "+0.250s ", "+1.000s "]);
+        logger.search_term = "+0.250s".to_string();
+        assert_eq!(logger.filtered_records()[0].message, "second");
+        logger.search_term.clear();
+        logger.time_precision = TimePrecision::Seconds;
+        assert_eq!(times(&logger), vec!["+0s ", "+0s ", "+1s "]);
+
+        logger.time_format = TimeFormat::SinceStart;
+        assert!(times(&logger)[2].starts_with("0:00:0"));
+        assert!(times(&logger)[0].starts_with("-0:00:0"));
+
+        logger.time_format = TimeFormat::Relative;
+        assert_eq!(logger.format_time(now - chrono::TimeDelta::seconds(90)), "1m ago ");
+        assert_eq!(logger.format_time(now - chrono::TimeDelta::hours(5)), "5h ago ");
+
+        logger.time_format = TimeFormat::Custom;
+        logger.custom_time_format = "%Y/%m".to_string();
+        assert_eq!(times(&logger)[0], format!("{} ", now.format("%Y/%m")));
+        // Invalid patterns fall back to the local time rather than panicking.
+        logger.custom_time_format = "%Y %J".to_string();
+        assert!(!logger.custom_time_format_is_valid());
+        assert_eq!(logger.format_time(now), format!("{} ", now.format("%T")));
+    }
+
+    #[test]
+    fn test_clock_and_app_time() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let start = chrono::Local::now();
+        let clock = ManualClock::new(start);
+        let mut logger = EguiLogger::new();
+        logger.set_clock(clock.clone());
+        logger.show_categories = false;
+        logger.show_level = false;
+        logger.time_format = TimeFormat::SinceStart;
+
+        let frame = Arc::new(AtomicU64::new(120));
+        let frame_source = frame.clone();
+        logger.set_app_time(move || format!("f{}", frame_source.load(Ordering::Relaxed)));
+
+        logger.log_info(vec![LogCategory::Combat], "start");
+        clock.advance(chrono::TimeDelta::seconds(65));
+        frame.store(3900, Ordering::Relaxed);
+        logger.log_info(vec![LogCategory::Combat], "later");
+
+        let records = logger.filtered_records();
+        assert_eq!(records[0].timestamp, start);
+        assert_eq!(records[1].timestamp, start + chrono::TimeDelta::seconds(65));
+        assert_eq!(records[1].app_time.as_deref(), Some("f3900"));
+        assert_eq!(logger.format_record_text(records[1]), "0:01:05 f3900 later");
+
+        logger.app_time_display = AppTimeDisplay::Instead;
+        // Times are right aligned to the width of the regular time.
+        assert_eq!(logger.format_record_text(logger.filtered_records()[0]), "   f120 start");
+        logger.app_time_display = AppTimeDisplay::Hide;
+        assert_eq!(logger.format_record_text(logger.filtered_records()[0]), "0:00:00 start");
+
+        // Relative times are measured with the logger's clock.
+        logger.time_format = TimeFormat::Relative;
+        clock.advance(chrono::TimeDelta::minutes(2));
+        assert_eq!(logger.format_record_text(logger.filtered_records()[1]), " 2m ago later");
+
+        logger.clear_app_time();
+        logger.log_info(vec![LogCategory::Combat], "no frame");
+        assert_eq!(logger.filtered_records()[2].app_time, None);
+
+        // The app time counts towards the record's size.
+        let records = logger.filtered_records();
+        assert!(records[1].approximate_size() > records[2].approximate_size());
+    }
+
+    #[test]
+    fn test_time_zones() {
+        // A record saved by an older version, which stored a local timestamp, from a machine in UTC+9.
+        let json = r#"{"timestamp":"2024-05-01T18:30:00.250+09:00","level":"Info","categories":["Network"],"message":"hello"}"#;
+        let record: LogRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.timestamp.offset().local_minus_utc(), 9 * 3600);
+        assert_eq!(record.id, 0);
+
+        let mut logger = EguiLogger::new();
+        logger.log_record(record);
+        let record = logger.filtered_records()[0].clone();
+        logger.time_format = TimeFormat::LocalTime;
+
+        logger.time_zone = DisplayZone::Original;
+        assert_eq!(logger.format_record_time(&record), "18:30:00 ");
+        logger.time_zone = DisplayZone::Utc;
+        assert_eq!(logger.format_record_time(&record), "09:30:00 ");
+        logger.time_zone = DisplayZone::Fixed(-5 * 3600);
+        assert_eq!(logger.format_record_time(&record), "04:30:00 ");
+        logger.time_zone = DisplayZone::Local;
+        let local = record.timestamp.with_timezone(&chrono::Local);
+        assert_eq!(logger.format_record_time(&record), format!("{} ", local.format("%T")));
+
+        logger.time_format = TimeFormat::Custom;
+        logger.custom_time_format = "%H:%M %:z".to_string();
+        logger.time_zone = DisplayZone::Original;
+        assert_eq!(logger.format_record_time(&record), "18:30 +09:00 ");
+
+        // The original offset survives saving and loading.
+        let json = serde_json::to_string(&logger).unwrap();
+        let restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        let restored_record = restored.filtered_records()[0];
+        assert_eq!(restored_record.timestamp.offset(), record.timestamp.offset());
+        assert_eq!(restored.time_zone, DisplayZone::Original);
+    }
+
+    #[test]
+    fn test_time_range() {
+        use std::time::Duration;
+
+        let start = chrono::Local::now();
+        let clock = ManualClock::new(start);
+        let mut logger = EguiLogger::new();
+        logger.set_clock(clock.clone());
+        for minute in 0..10 {
+            logger.log_info(vec![LogCategory::Combat], format!("minute {minute}"));
+            clock.advance(chrono::TimeDelta::minutes(1));
+        }
+        // Now 10 minutes after the first record.
+        assert_eq!(logger.filtered_records().len(), 10);
+
+        logger.time_range = Some(TimeRange::Last(Duration::from_secs(5 * 60)));
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["minute 5", "minute 6", "minute 7", "minute 8", "minute 9"]);
+        assert_eq!(logger.time_range_text().as_deref(), Some("Last 5m"));
+
+        // The range moves with the clock.
+        clock.advance(chrono::TimeDelta::minutes(2));
+        assert_eq!(logger.filtered_records().len(), 3);
+
+        // "Show from here" replaces a relative range.
+        let times: Vec<_> = logger.sorted_records().iter().map(|r| r.timestamp).collect();
+        logger.show_from(times[2]);
+        assert_eq!(logger.filtered_records().len(), 8);
+        assert_eq!(logger.filtered_records()[0].message, "minute 2");
+
+        // "Show until here" keeps the start, giving an inclusive range.
+        logger.show_until(times[4]);
+        let messages: Vec<&str> = logger.filtered_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["minute 2", "minute 3", "minute 4"]);
+        assert_eq!(
+            logger.time_range,
+            Some(TimeRange::Between {
+                start: Some(times[2]),
+                end: Some(times[4])
+            })
+        );
+
+        // A start after the end drops the end.
+        logger.show_from(times[6]);
+        assert_eq!(
+            logger.time_range,
+            Some(TimeRange::Between {
+                start: Some(times[6]),
+                end: None
+            })
+        );
+        assert_eq!(logger.filtered_records().len(), 4);
+
+        let json = serde_json::to_string(&logger).unwrap();
+        let restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.time_range, logger.time_range);
+
+        logger.time_range = None;
+        assert_eq!(logger.filtered_records().len(), 10);
+        assert_eq!(logger.time_range_text(), None);
+    }
+
+    #[test]
+    fn test_bookmarks_and_pins() {
+        let mut logger = EguiLogger::new();
+        for n in 0..5 {
+            logger.log_info(vec![LogCategory::Combat], format!("hit {n}"));
+        }
+        let ids: Vec<u64> = logger.filtered_records().iter().map(|r| r.id).collect();
+
+        assert!(logger.bookmark_record(ids[1], "boss appears"));
+        assert!(logger.bookmark_record(ids[3], ""));
+        assert!(!logger.bookmark_record(999, "missing"));
+        // Notes count towards the memory budget.
+        let usage = logger.memory_usage();
+        logger.bookmark_record(ids[2], "a long note about what happened here");
+        assert!(logger.memory_usage() > usage);
+        logger.remove_bookmark(ids[2]);
+        assert_eq!(logger.memory_usage(), usage);
+        let bookmarked: Vec<&str> = logger.bookmarked_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(bookmarked, ["hit 1", "hit 3"]);
+        let details = logger.record_details(logger.bookmarked_records()[0]);
+        assert!(details.contains(&("Bookmark", "boss appears".to_string())));
+
+        // Changing the note keeps the bookmark; removing it drops it from the list.
+        logger.bookmark_record(ids[3], "loot");
+        assert_eq!(logger.bookmarked_records()[1].bookmark.as_deref(), Some("loot"));
+        logger.show_only_bookmarked = true;
+        assert_eq!(logger.filtered_records().len(), 2);
+        assert!(logger.remove_bookmark(ids[3]));
+        assert_eq!(logger.filtered_records().len(), 1);
+        logger.show_only_bookmarked = false;
+
+        // Pinned records are listed even when the filters hide them.
+        logger.select_record(ids[0]);
+        logger.toggle_record_selected(ids[4]);
+        logger.pin_selected();
+        logger.min_display_level = LogLevel::Warn;
+        let pinned: Vec<&str> = logger.pinned_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(pinned, ["hit 0", "hit 4"]);
+        logger.min_display_level = LogLevel::Debug;
+
+        // Changes while paused apply to both the paused view and the stored records.
+        logger.pause();
+        logger.set_pinned(ids[4], false);
+        assert_eq!(logger.pinned_records().len(), 1);
+        logger.resume();
+        assert_eq!(logger.pinned_records().len(), 1);
+
+        // Bookmarks and pins are kept when the logger is saved and loaded.
+        let json = serde_json::to_string(&logger).unwrap();
+        let restored: EguiLogger = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.bookmarked_records()[0].bookmark.as_deref(),
+            Some("boss appears")
+        );
+        assert_eq!(restored.pinned_records()[0].message, "hit 0");
+
+        // And when records are exported and logged into another logger.
+        let exported = serde_json::to_string(&logger.filtered_records()).unwrap();
+        let mut other = EguiLogger::new();
+        for record in serde_json::from_str::<Vec<LogRecord>>(&exported).unwrap() {
+            other.log_record(record);
+        }
+        assert_eq!(other.bookmarked_records()[0].bookmark.as_deref(), Some("boss appears"));
+        assert_eq!(other.pinned_records().len(), 1);
+
+        logger.unpin_all();
+        assert!(logger.pinned_records().is_empty());
+    }
+
+    #[test]
+    fn test_timeline() {
+        use crate::timeline::Timeline;
+        use std::time::Duration;
+
+        let start = chrono::Local::now();
+        let clock = ManualClock::new(start);
+        let mut logger = EguiLogger::new();
+        logger.set_clock(clock.clone());
+        assert_eq!(logger.timeline(10), None);
+        assert!(!logger.jump_to_time(start.fixed_offset()));
+
+        // Ten seconds of records with a burst of errors in the last second.
+        for _ in 0..10 {
+            logger.log_info(vec![LogCategory::Network], "tick");
+            clock.advance(chrono::TimeDelta::seconds(1));
+        }
+        logger.log_error(vec![LogCategory::Network], "timeout");
+        logger.log_error(vec![LogCategory::Network], "timeout again");
+        logger.log_debug(vec![LogCategory::UI], "redraw");
+
+        let timeline = logger.timeline(10).unwrap();
+        assert_eq!(timeline.start, start.fixed_offset());
+        assert_eq!(timeline.counts.len(), 10);
+        assert_eq!(timeline.count(0, LogLevel::Info), 1);
+        assert_eq!(timeline.count(9, LogLevel::Error), 2);
+        assert_eq!(timeline.count(9, LogLevel::Debug), 1);
+        // The last bin includes the end of the timeline, so it also has the last tick.
+        assert_eq!(timeline.count(9, LogLevel::Info), 1);
+        assert_eq!(timeline.max_total(), 4);
+        let total: u32 = timeline.counts.iter().flatten().sum();
+        assert_eq!(total, 13);
+
+        // The timeline follows the filters, except the time range.
+        logger.min_display_level = LogLevel::Info;
+        logger.time_range = Some(TimeRange::Last(Duration::from_secs(1)));
+        let timeline = logger.timeline(10).unwrap();
+        assert_eq!(timeline.count(9, LogLevel::Debug), 0);
+        assert_eq!(timeline.counts.iter().flatten().sum::<u32>(), 12);
+
+        // Dragging across the strip turns positions into a time range.
+        let (bin_start, bin_end) = timeline.bin_range(2);
+        assert_eq!(timeline.time_at(0.2), bin_start);
+        assert_eq!(timeline.time_at(0.3), bin_end);
+        assert_eq!(timeline.fraction_of(timeline.end), 1.0);
+        logger.time_range = Some(TimeRange::Between {
+            start: Some(timeline.time_at(0.0)),
+            end: Some(timeline.time_at(0.25)),
+        });
+        assert_eq!(logger.filtered_records().len(), 3);
+
+        // Clicking jumps to the first displayed record at or after that time.
+        logger.time_range = None;
+        assert!(logger.jump_to_time(timeline.time_at(0.5)));
+        let selected = logger.detail_record().unwrap();
+        assert_eq!(
+            selected.timestamp,
+            (start + chrono::TimeDelta::seconds(5)).fixed_offset()
+        );
+        assert!(logger.take_scroll_to_selection());
+        // Past the newest record, the newest is picked.
+        assert!(logger.jump_to_time(timeline.end + chrono::TimeDelta::hours(1)));
+        assert_eq!(logger.detail_record().unwrap().message, "timeout again");
+
+        // A single record still gets a span, and repeats count towards its bin.
+        let mut record = EguiLogger::get_log_record(LogLevel::Warn, vec![LogCategory::Save], "slow save");
+        record.repeats = 4;
+        let timeline = Timeline::new(&[&record], 3).unwrap();
+        assert_eq!(timeline.end - timeline.start, chrono::TimeDelta::seconds(1));
+        assert_eq!(timeline.count(0, LogLevel::Warn), 5);
+    }
+
+    #[test]
+    fn test_load_baseline_logger() {
+        // A logger saved before records had ids, limits, tabs, ... and with a record from UTC+9.
+        let json = r#"{"records":{"Error":[],"Warn":[],"Debug":[],"Info":[
+            {"timestamp":"2024-05-01T18:30:00.250+09:00","level":"Info","categories":["Network"],"message":"hello"},
+            {"timestamp":"2024-05-01T18:30:01.250+09:00","level":"Info","categories":["Network"],"message":"again"}]},
+            "show_settings":true,"category_counts":{"Network":2},"min_display_level":"Debug",
+            "hidden_categories":["Combat"],"time_format":"LocalTime","time_precision":"Seconds",
+            "show_categories":true,"show_level":true,"show_search":false,"max_message_length":2000,
+            "max_records_per_level":2000,"search_term":"","search_with_regex":false,
+            "search_with_case_sensitive":false,"show_input_area":true,"input_text":"",
+            "input_hint":"Type a message and press Enter...","input_text_prefix":"","input_categories":["Input"],
+            "input_level":"Info"}"#;
+        let mut logger: EguiLogger = serde_json::from_str(json).unwrap();
+
+        let records = logger.filtered_records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].timestamp.offset().local_minus_utc(), 9 * 3600);
+        // Records get unique ids, in the order they were logged.
+        assert_eq!((records[0].id, records[1].id), (0, 1));
+        assert!(logger.memory_usage() > 0);
+        assert_eq!(logger.min_capture_level, EguiLogger::new().min_capture_level);
+        assert!(logger.hidden_categories().contains("Combat"));
+
+        logger.log_info(vec![LogCategory::Network], "new");
+        assert_eq!(logger.filtered_records()[2].id, 2);
+        logger.select_record(0);
+        assert_eq!(logger.selected_records().len(), 1);
+    }
+
+    #[test]
+    fn test_archive_shared_across_sessions() {
+        let path = std::env::temp_dir().join(format!("egui_logger_shared_archive_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // The first session archives "old 0" and "old 1", which were stored with ids 0 and 1.
+        let mut first = EguiLogger::new();
+        first.max_records_per_level = 2;
+        first.enable_archive(&path);
+        for i in 0..4 {
+            first.log_info(vec![LogCategory::Save], format!("old {i}"));
+        }
+
+        // A fresh logger starts its ids at 0 again.
+        let mut logger = EguiLogger::new();
+        logger.enable_archive(&path);
+        assert!(logger.archive().unwrap().has_older());
+        logger.log_info(vec![LogCategory::Save], "new 0");
+        logger.log_info(vec![LogCategory::Save], "new 1");
+        logger.set_scrolled_to_bottom(false);
+        logger.log_info(vec![LogCategory::Save], "new 2");
+
+        logger.pause();
+        assert_eq!(logger.load_older_archived().unwrap(), 0);
+        logger.resume();
+        assert_eq!(logger.load_older_archived().unwrap(), 2);
+        let records = logger.filtered_records();
+        let mut ids: Vec<u64> = records.iter().map(|r| r.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 5);
+
+        let new_0 = records.iter().find(|r| r.message == "new 0").unwrap().id;
+        assert!(logger.bookmark_record(new_0, "note"));
+        let bookmarked: Vec<&str> = logger.bookmarked_records().iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(bookmarked, ["new 0"]);
+
+        logger.select_record(new_0);
+        assert_eq!(logger.selected_records().len(), 1);
+        assert_eq!(logger.selected_text().lines().count(), 1);
+        assert_eq!(logger.detail_record().unwrap().message, "new 0");
+
+        // Loaded records are older than the unread ones, even though their ids are newer.
+        let unread: Vec<&str> = logger
+            .filtered_records()
+            .into_iter()
+            .filter(|r| logger.is_unread(r))
+            .map(|r| r.message.as_str())
+            .collect();
+        assert_eq!(unread, ["new 2"]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_evicted_marker_with_archive() {
+        let path = std::env::temp_dir().join(format!("egui_logger_marker_archive_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut logger = EguiLogger::new();
+        logger.max_records_per_level = 2;
+        logger.log_info(vec![LogCategory::Save], "before archiving");
+        logger.log_info(vec![LogCategory::Save], "kept 1");
+        logger.log_info(vec![LogCategory::Save], "kept 2");
+        assert!(
+            logger
+                .evicted_marker_text()
+                .unwrap()
+                .starts_with("[1 older record was dropped: 1 INFO")
+        );
+
+        // Archived records can be loaded back, so they aren't reported as dropped.
+        logger.enable_archive(&path);
+        for i in 0..3 {
+            logger.log_info(vec![LogCategory::Save], format!("archived {i}"));
+        }
+        assert_eq!(logger.evicted_count(LogLevel::Info), 4);
+        assert!(
+            logger
+                .evicted_marker_text()
+                .unwrap()
+                .starts_with("[1 older record was dropped: 1 INFO")
+        );
+        logger.load_older_archived().unwrap();
+        assert!(
+            logger
+                .evicted_marker_text()
+                .unwrap()
+                .starts_with("[1 older record was dropped: 1 INFO")
+        );
+
+        logger.clear();
+        logger.log_info(vec![LogCategory::Save], "a");
+        logger.log_info(vec![LogCategory::Save], "b");
+        logger.log_info(vec![LogCategory::Save], "c");
+        assert_eq!(logger.evicted_count(LogLevel::Info), 1);
+        assert_eq!(logger.evicted_marker_text(), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -0,0 +1,74 @@
+use chrono::{DateTime, FixedOffset, TimeDelta};
+
+use crate::record::LogRecord;
+use crate::types::LogLevel;
+
+/// Record counts per level over time, for the timeline strip above the log area.
+/// The span from the oldest to the newest record is split into bins of equal length.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Timeline {
+    pub(crate) start: DateTime<FixedOffset>,
+    pub(crate) end: DateTime<FixedOffset>,
+    /// Number of records in each bin, indexed by level, from `Debug` to `Error`.
+    pub(crate) counts: Vec<[u32; 4]>,
+}
+
+impl Timeline {
+    /// Bins the records, which don't need to be sorted. `None` if there are no records.
+    /// Repeats count towards the bin of the record they were merged into.
+    pub(crate) fn new(records: &[&LogRecord], bins: usize) -> Option<Self> {
+        let start = records.iter().map(|r| r.timestamp).min()?;
+        let end = records.iter().map(|r| r.timestamp).max()?;
+        // A span of at least a second, so a single record or a burst still gets a strip.
+        let end = end.max(start + TimeDelta::seconds(1));
+        let mut timeline = Self {
+            start,
+            end,
+            counts: vec![[0; 4]; bins.max(1)],
+        };
+        for record in records {
+            let bin = timeline.bin_at(record.timestamp);
+            timeline.counts[bin][record.level as usize] += 1 + record.repeats;
+        }
+        Some(timeline)
+    }
+
+    fn span_nanos(&self) -> f64 {
+        (self.end - self.start).num_nanoseconds().unwrap_or(i64::MAX) as f64
+    }
+
+    /// Index of the bin containing `time`. Times outside of the timeline go in the first or last bin.
+    pub(crate) fn bin_at(&self, time: DateTime<FixedOffset>) -> usize {
+        let offset = (time - self.start).num_nanoseconds().unwrap_or(i64::MAX) as f64;
+        let bin = (offset / self.span_nanos() * self.counts.len() as f64) as usize;
+        bin.min(self.counts.len() - 1)
+    }
+
+    /// The time at a fraction of the way along the timeline, from 0.0 to 1.0.
+    pub(crate) fn time_at(&self, fraction: f64) -> DateTime<FixedOffset> {
+        let nanos = self.span_nanos() * fraction.clamp(0.0, 1.0);
+        self.start + TimeDelta::nanoseconds(nanos as i64)
+    }
+
+    /// The fraction of the way along the timeline of `time`, clamped to 0.0 to 1.0.
+    pub(crate) fn fraction_of(&self, time: DateTime<FixedOffset>) -> f64 {
+        let offset = (time - self.start).num_nanoseconds().unwrap_or(i64::MAX) as f64;
+        (offset / self.span_nanos()).clamp(0.0, 1.0)
+    }
+
+    /// Start and end of a bin.
+    pub(crate) fn bin_range(&self, bin: usize) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
+        let bins = self.counts.len() as f64;
+        (self.time_at(bin as f64 / bins), self.time_at((bin + 1) as f64 / bins))
+    }
+
+    /// The largest total count of a bin, for scaling the bars.
+    pub(crate) fn max_total(&self) -> u32 {
+        self.counts.iter().map(|c| c.iter().sum()).max().unwrap_or(0)
+    }
+
+    /// The number of records of a level in a bin.
+    pub(crate) fn count(&self, bin: usize, level: LogLevel) -> u32 {
+        self.counts[bin][level as usize]
+    }
+}
@@ -1,5 +1,9 @@
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 
+use crate::categories::IntoCategories;
+use crate::record::LogRecord;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub enum LogLevel {
     Error = 3,
@@ -25,6 +29,107 @@ pub enum TimeFormat {
     Utc,
     LocalTime,
     Hide,
+    /// Time since the logger was created, e.g. "0:01:23".
+    SinceStart,
+    /// Time since the previous record, e.g. "+0.250s". Other times, like when a repeated record
+    /// last occurred, are shown as in `SinceStart`.
+    Delta,
+    /// How long ago the record was logged, e.g. "12s ago". The view refreshes to keep it current.
+    Relative,
+    /// Local time formatted with [`crate::EguiLogger::custom_time_format`], a strftime pattern.
+    Custom,
+}
+
+/// The time zone that times are shown in, for `TimeFormat::LocalTime` and `TimeFormat::Custom`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayZone {
+    /// The zone of the machine showing the log.
+    #[default]
+    Local,
+    Utc,
+    /// The zone each record was logged in, e.g. for records received from a server elsewhere.
+    Original,
+    /// A fixed offset from UTC, in seconds east of UTC.
+    Fixed(i32),
+}
+
+/// How a record's app time (see [`crate::EguiLogger::set_app_time`]) is shown.
+/// Records without an app time always show their regular time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AppTimeDisplay {
+    Hide,
+    /// After the regular time.
+    #[default]
+    Alongside,
+    /// In place of the regular time.
+    Instead,
+}
+
+/// How records are laid out in the log area.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLayout {
+    /// Each record is a single line of text.
+    #[default]
+    Text,
+    /// Each record is a row, with a column per field. See [`crate::EguiLogger::table_columns`].
+    Table,
+}
+
+/// A column of the [`LogLayout::Table`] layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LogColumn {
+    Time,
+    Level,
+    Categories,
+    Message,
+}
+
+impl LogColumn {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogColumn::Time => "Time",
+            LogColumn::Level => "Level",
+            LogColumn::Categories => "Categories",
+            LogColumn::Message => "Message",
+        }
+    }
+
+    /// Orders records by the value shown in this column.
+    pub(crate) fn compare(&self, a: &LogRecord, b: &LogRecord) -> std::cmp::Ordering {
+        match self {
+            LogColumn::Time => a.timestamp.cmp(&b.timestamp),
+            LogColumn::Level => a.level.cmp(&b.level),
+            LogColumn::Categories => a.categories.cmp(&b.categories),
+            LogColumn::Message => a.message.cmp(&b.message),
+        }
+    }
+}
+
+/// Settings for a column of the [`LogLayout::Table`] layout.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TableColumn {
+    pub column: LogColumn,
+    /// Width in points. The last visible column fills the remaining width instead.
+    pub width: f32,
+    pub visible: bool,
+}
+
+impl TableColumn {
+    pub fn new(column: LogColumn, width: f32) -> Self {
+        Self {
+            column,
+            width,
+            visible: true,
+        }
+    }
+}
+
+/// Where the detail pane for the selected record is shown, relative to the log area.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DetailsPosition {
+    #[default]
+    Right,
+    Bottom,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -32,3 +137,81 @@ pub enum TimePrecision {
     Seconds,
     Milliseconds,
 }
+
+/// Whether records identical to a previous one (same level, categories and message) are merged
+/// into that record rather than stored separately. Merged records are displayed once, with a
+/// repeat count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum RepeatCollapse {
+    /// Every record is stored separately.
+    #[default]
+    Off,
+    /// Merge into the most recently logged record.
+    Consecutive,
+    /// Merge into a record of the same level that was last repeated within the given duration,
+    /// even if other records were logged in between.
+    Within(std::time::Duration),
+}
+
+/// Limits the displayed records to those logged within a span of time.
+/// See [`crate::EguiLogger::time_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TimeRange {
+    /// Records logged within this duration before now. The range moves as time passes.
+    Last(std::time::Duration),
+    /// Records logged between `start` and `end`, inclusive. `None` leaves that side open.
+    Between {
+        start: Option<DateTime<FixedOffset>>,
+        end: Option<DateTime<FixedOffset>>,
+    },
+}
+
+impl TimeRange {
+    /// The start and end of the range, given the current time.
+    pub fn bounds(&self, now: DateTime<FixedOffset>) -> (Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>) {
+        match *self {
+            TimeRange::Last(duration) => {
+                let start = chrono::TimeDelta::from_std(duration)
+                    .ok()
+                    .and_then(|d| now.checked_sub_signed(d));
+                (start, None)
+            }
+            TimeRange::Between { start, end } => (start, end),
+        }
+    }
+
+    /// Whether `time` is within the range, given the current time.
+    pub fn contains(&self, time: DateTime<FixedOffset>, now: DateTime<FixedOffset>) -> bool {
+        let (start, end) = self.bounds(now);
+        start.is_none_or(|start| time >= start) && end.is_none_or(|end| time <= end)
+    }
+}
+
+/// Text submitted through the input area that wasn't run as a command.
+/// See [`crate::EguiLogger::take_submitted_inputs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmittedInput {
+    /// The text as typed, without `input_text_prefix`.
+    pub text: String,
+    /// The input level at the time of submission.
+    pub level: LogLevel,
+    /// The input categories at the time of submission.
+    pub categories: Vec<String>,
+}
+
+/// A named preset of categories that user input is logged with, e.g. "Party" or "Guild".
+/// See [`crate::EguiLogger::set_input_channels`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputChannel {
+    pub name: String,
+    pub categories: Vec<String>,
+}
+
+impl InputChannel {
+    pub fn new<C: IntoCategories>(name: impl Into<String>, categories: C) -> Self {
+        Self {
+            name: name.into(),
+            categories: categories.into_categories(),
+        }
+    }
+}
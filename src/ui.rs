@@ -1,11 +1,24 @@
-use egui::{Align, Color32, FontSelection, PopupCloseBehavior, RichText, Style, UiBuilder, text::LayoutJob};
+use egui::text::{CCursor, CCursorRange, LayoutJob};
+use egui::{
+    Align, Color32, FontSelection, Key, KeyboardShortcut, Modifiers, PopupCloseBehavior, RectAlign, RichText, Style,
+    UiBuilder,
+};
 
 use crate::logger::EguiLogger;
 use crate::record::LogRecord;
-use crate::types::{LogLevel, TimeFormat, TimePrecision};
+use crate::types::{
+    AppTimeDisplay, DetailsPosition, DisplayZone, LogLayout, LogLevel, TableColumn, TimeFormat, TimePrecision,
+    TimeRange,
+};
+use chrono::{DateTime, FixedOffset};
+use std::time::Duration;
 
 pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
     let time_padding = logger.get_time_format_padding();
+    if logger.time_format == TimeFormat::Relative || matches!(logger.time_range, Some(TimeRange::Last(_))) {
+        // Keep "12s ago" and the "Last 5m" range up to date.
+        ui.ctx().request_repaint_after(std::time::Duration::from_secs(1));
+    }
 
     // --- Top Controls ---
     if logger.show_settings {
@@ -15,21 +28,19 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
             }
 
             if ui.button("Copy").clicked() {
-                // Collect, filter, then sort records for a chronological copy.
-                let mut records_to_copy: Vec<&LogRecord> = logger
-                    .records()
-                    .values()
-                    .flatten()
-                    .filter(|record| logger.matches_filters(record))
-                    .collect();
-                records_to_copy.sort_by_key(|r| r.timestamp);
+                // Filtered records are sorted, for a chronological copy.
+                let records_to_copy = logger.filtered_records();
 
                 let mut out_string = String::new();
+                if let Some(marker) = logger.evicted_marker_text() {
+                    out_string.push_str(&marker);
+                    out_string.push('\n');
+                }
                 for record in records_to_copy {
                     out_string.push_str(
                         format_record(logger, record, time_padding, ui).text.as_str(), // Use existing time_padding
                     );
-                    out_string.push_str("\n"); // Use newline for better copy-paste
+                    out_string.push('\n'); // Use newline for better copy-paste
                 }
                 ui.ctx().copy_text(out_string);
             };
@@ -38,7 +49,7 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
                 .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
                 .show(|ui| {
                     ui.menu_button("Log Levels", |ui| {
-                        for level in vec![LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug] {
+                        for level in [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug] {
                             if ui
                                 .selectable_label(logger.min_display_level <= level, level.as_str())
                                 .clicked()
@@ -72,8 +83,62 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
                             }
                         }
                     });
+
+                    ui.menu_button("Time Range", |ui| show_time_range_menu(logger, ui));
+
+                    if ui
+                        .selectable_label(logger.show_only_bookmarked, "Bookmarked Only")
+                        .clicked()
+                    {
+                        logger.show_only_bookmarked = !logger.show_only_bookmarked;
+                    }
                 });
 
+            egui::Popup::menu(&ui.button("Bookmarks"))
+                .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+                .show(|ui| show_bookmarks_menu(logger, ui));
+
+            if let Some(range_text) = logger.time_range_text()
+                && ui
+                    .button(format!("{range_text} ✖"))
+                    .on_hover_text("Show records from any time")
+                    .clicked()
+            {
+                logger.time_range = None;
+            }
+
+            if ui
+                .button("New Tab")
+                .on_hover_text("Open a tab with the current filters")
+                .clicked()
+            {
+                logger.add_tab_from_filters();
+            }
+
+            if ui
+                .selectable_label(logger.follow_newest, "Follow")
+                .on_hover_text("Keep scrolling to new records")
+                .clicked()
+            {
+                logger.follow_newest = !logger.follow_newest;
+            }
+
+            let pause_text = match logger.arrived_while_paused() {
+                0 => "Pause".to_string(),
+                n => format!("Paused ({n} new)"),
+            };
+            if ui
+                .selectable_label(logger.is_paused(), pause_text)
+                .on_hover_text("Freeze the displayed records while new ones keep being stored")
+                .clicked()
+            {
+                if logger.is_paused() {
+                    logger.resume();
+                } else {
+                    logger.pause();
+                }
+            }
+
             if ui.button("Search").clicked() {
                 logger.show_search = !logger.show_search;
                 if logger.show_search {
@@ -87,8 +152,53 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
                     ui.menu_button("Time", |ui| {
                         ui.radio_value(&mut logger.time_format, TimeFormat::Utc, "UTC");
                         ui.radio_value(&mut logger.time_format, TimeFormat::LocalTime, "Local Time");
+                        ui.radio_value(&mut logger.time_format, TimeFormat::SinceStart, "Since Start");
+                        ui.radio_value(&mut logger.time_format, TimeFormat::Delta, "Since Previous");
+                        ui.radio_value(&mut logger.time_format, TimeFormat::Relative, "Relative");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut logger.time_format, TimeFormat::Custom, "Custom");
+                            let valid = logger.custom_time_format_is_valid();
+                            let response = ui
+                                .add(egui::TextEdit::singleline(&mut logger.custom_time_format).desired_width(140.0))
+                                .on_hover_text("strftime pattern, e.g. %Y-%m-%d %H:%M:%S");
+                            if response.changed() {
+                                logger.time_format = TimeFormat::Custom;
+                            }
+                            if !valid {
+                                ui.colored_label(ui.visuals().error_fg_color, "Invalid");
+                            }
+                        });
                         ui.radio_value(&mut logger.time_format, TimeFormat::Hide, "Hide");
                         ui.separator();
+                        ui.label("Zone:");
+                        ui.radio_value(&mut logger.time_zone, DisplayZone::Local, "Local");
+                        ui.radio_value(&mut logger.time_zone, DisplayZone::Utc, "UTC");
+                        ui.radio_value(&mut logger.time_zone, DisplayZone::Original, "Original")
+                            .on_hover_text("The zone each record was logged in");
+                        ui.horizontal(|ui| {
+                            let mut offset = match logger.time_zone {
+                                DisplayZone::Fixed(offset) => offset,
+                                _ => 0,
+                            };
+                            ui.radio_value(&mut logger.time_zone, DisplayZone::Fixed(offset), "Fixed");
+                            let mut hours = offset as f32 / 3600.0;
+                            let drag = egui::DragValue::new(&mut hours)
+                                .range(-14.0..=14.0)
+                                .speed(0.25)
+                                .fixed_decimals(2)
+                                .prefix("UTC ");
+                            if ui.add(drag).changed() {
+                                // Offsets are in quarter hours, which covers all real zones.
+                                offset = ((hours * 4.0).round() * 900.0) as i32;
+                                logger.time_zone = DisplayZone::Fixed(offset);
+                            }
+                        });
+                        ui.separator();
+                        ui.label("App time:");
+                        ui.radio_value(&mut logger.app_time_display, AppTimeDisplay::Alongside, "Alongside");
+                        ui.radio_value(&mut logger.app_time_display, AppTimeDisplay::Instead, "Instead");
+                        ui.radio_value(&mut logger.app_time_display, AppTimeDisplay::Hide, "Hide");
+                        ui.separator();
                         ui.radio_value(&mut logger.time_precision, TimePrecision::Seconds, "Seconds");
                         ui.radio_value(&mut logger.time_precision, TimePrecision::Milliseconds, "Milliseconds");
                     });
@@ -98,10 +208,40 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
                     if ui.selectable_label(logger.show_level, "Show Log Level").clicked() {
                         logger.show_level = !logger.show_level;
                     }
+                    ui.menu_button("Layout", |ui| {
+                        ui.radio_value(&mut logger.layout, LogLayout::Text, "Text");
+                        ui.radio_value(&mut logger.layout, LogLayout::Table, "Table");
+                        if logger.layout == LogLayout::Table {
+                            ui.separator();
+                            show_column_toggles(logger, ui);
+                        }
+                    });
+                    if ui.selectable_label(logger.select_text, "Select Text").clicked() {
+                        logger.select_text = !logger.select_text;
+                    }
+                    if ui.selectable_label(logger.show_details, "Show Details").clicked() {
+                        logger.show_details = !logger.show_details;
+                    }
+                    if logger.show_details {
+                        ui.horizontal(|ui| {
+                            ui.label("Details: ");
+                            ui.radio_value(&mut logger.details_position, DetailsPosition::Right, "Right");
+                            ui.radio_value(&mut logger.details_position, DetailsPosition::Bottom, "Bottom");
+                        });
+                    }
+                    if ui.selectable_label(logger.show_timeline, "Show Timeline").clicked() {
+                        logger.show_timeline = !logger.show_timeline;
+                    }
                     if ui.selectable_label(logger.show_input_area, "Show Input Area").clicked() {
                         logger.show_input_area = !logger.show_input_area;
                     }
                     if logger.show_input_area {
+                        if ui
+                            .selectable_label(logger.multiline_input, "Multi-line Input")
+                            .clicked()
+                        {
+                            logger.multiline_input = !logger.multiline_input;
+                        }
                         ui.horizontal(|ui| {
                             ui.label("Input area hint: ");
                             ui.text_edit_singleline(&mut logger.input_hint);
@@ -159,9 +299,22 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
         ui.separator(); // Separator after search bar
     }
 
-    // Calculate the height needed for the input area
+    if !logger.tabs().is_empty() {
+        show_tab_bar(logger, ui);
+        ui.separator();
+    }
+
+    // Keys for the selected records are only handled when no text field has focus.
+    if logger.has_selection() && ui.memory(|m| m.focused().is_none()) {
+        handle_selection_keys(logger, ui);
+    }
+
+    // The input area is measured after it's shown, so its space is reserved with the height it had
+    // in the previous frame. Until it has been measured, a single row is assumed.
     let input_height = if logger.show_input_area {
-        ui.spacing().interact_size.y + ui.spacing().item_spacing.y * 2.0 // Approximate height
+        logger
+            .input_area_height()
+            .unwrap_or(ui.spacing().interact_size.y + ui.spacing().item_spacing.y * 2.0)
     } else {
         0.0
     };
@@ -172,59 +325,136 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
 
     // Create log display area with calculated height
     if log_area_height > 0.0 {
-        let log_rect = egui::Rect::from_min_size(
+        let mut log_rect = egui::Rect::from_min_size(
             available_rect.min,
             egui::Vec2::new(available_rect.width(), log_area_height),
         );
+        if logger.show_details {
+            log_rect = show_details_pane(logger, ui, log_rect);
+        }
 
         let mut log_ui = ui.new_child(
             UiBuilder::new()
                 .max_rect(log_rect)
                 .layout(egui::Layout::top_down(Align::LEFT)),
         );
+        if logger.show_timeline {
+            show_timeline(logger, &mut log_ui);
+        }
+        show_pinned_records(logger, &mut log_ui, time_padding);
+        if logger.layout == LogLayout::Table {
+            show_table_header(logger, &mut log_ui);
+        }
         // --- Log Display Area (Central Scroll Area) ---
         // This `ScrollArea` will use the space remaining in `ui` after the top controls
         // and the bottom input panel have been laid out.
-        egui::ScrollArea::vertical()
+        let mut unread = 0;
+        let scroll_output = egui::ScrollArea::vertical()
             .auto_shrink([false, false]) // Fill available width and height. Crucial.
-            .stick_to_bottom(true)
+            .stick_to_bottom(logger.follow_newest)
             .show(&mut log_ui, |scroll_ui| {
-                let mut all_records: Vec<&LogRecord> = logger.records().values().flatten().collect();
-                all_records.sort_by_key(|r| r.timestamp);
-
-                if all_records.is_empty() && !logger.show_input_area {
-                    scroll_ui.label("No logs to display.");
-                }
+                let scroll_to_selection = logger.take_scroll_to_selection();
+                let mut clicked = None;
+                let mut action = None;
+                let records = logger.displayed_records();
 
-                all_records.into_iter().for_each(|record| {
-                    if !logger.matches_filters(&record) {
-                        return;
+                let mut load_older = false;
+                if let Some(archive) = logger.archive() {
+                    // Loaded records wouldn't be shown while paused.
+                    if archive.has_older() && !logger.is_paused() {
+                        load_older = scroll_ui.button("Load older…").clicked();
                     }
+                    if let Some(error) = archive.last_error() {
+                        scroll_ui.colored_label(scroll_ui.visuals().error_fg_color, format!("Archive error: {error}"));
+                    }
+                }
 
-                    let layout_job = format_record(logger, &record, time_padding, scroll_ui);
-                    let raw_text = layout_job.text.clone(); // Still needed for copy in context menu
+                if records.is_empty() && !logger.show_input_area {
+                    scroll_ui.label("No logs to display.");
+                }
 
-                    let response = scroll_ui.label(layout_job);
+                if let Some(marker) = logger.evicted_marker_text() {
+                    scroll_ui.label(RichText::new(marker).monospace().weak());
+                }
 
-                    response.clone().context_menu(|menu_ui| {
-                        if logger.show_categories {
-                            menu_ui.label(&record.categories.join(","));
+                records.into_iter().for_each(|record| {
+                    if logger.is_unread(record) {
+                        if unread == 0 {
+                            show_unread_divider(scroll_ui);
                         }
-                        if menu_ui.button("Copy").clicked() {
-                            menu_ui.ctx().copy_text(raw_text);
-                            menu_ui.close();
+                        unread += 1;
+                    }
+
+                    // Selected rows get a background, which has to be painted before the text.
+                    let background = scroll_ui.painter().add(egui::Shape::Noop);
+                    let (response, raw_text) = match logger.layout {
+                        LogLayout::Text => {
+                            let layout_job = format_record(logger, record, time_padding, scroll_ui);
+                            let raw_text = layout_job.text.clone(); // Still needed for copy in context menu
+                            let label = egui::Label::new(layout_job).selectable(logger.select_text);
+                            (scroll_ui.add(label.sense(egui::Sense::click())), raw_text)
                         }
-                    });
+                        LogLayout::Table => (
+                            show_table_row(logger, scroll_ui, record),
+                            logger.format_record_text(record),
+                        ),
+                    };
+                    if logger.is_record_selected(record.id) {
+                        let row = egui::Rect::from_x_y_ranges(scroll_ui.max_rect().x_range(), response.rect.y_range());
+                        let fill = scroll_ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+                        scroll_ui
+                            .painter()
+                            .set(background, egui::Shape::rect_filled(row, 0.0, fill));
+                    }
+                    if scroll_to_selection && logger.selection_cursor() == Some(record.id) {
+                        response.scroll_to_me(None);
+                    }
+                    if response.clicked() {
+                        clicked = Some((record.id, scroll_ui.input(|i| i.modifiers)));
+                    }
+
+                    paint_bookmark_marker(scroll_ui, record, &response);
+                    show_record_menu(logger, &response, record, raw_text, &mut action);
                 });
+
+                if let Some(action) = action {
+                    action.apply(logger);
+                }
+
+                if let Some((id, modifiers)) = clicked {
+                    if modifiers.shift {
+                        logger.select_record_range(id);
+                    } else if modifiers.command {
+                        logger.toggle_record_selected(id);
+                    } else {
+                        logger.select_record(id);
+                    }
+                }
+
+                if logger.take_scroll_to_newest() {
+                    scroll_ui.scroll_to_cursor(Some(Align::BOTTOM));
+                }
+
+                if load_older {
+                    // Errors are kept by the archive and shown above.
+                    let _ = logger.load_older_archived();
+                }
             });
+
+        let scroll_bottom = scroll_output.state.offset.y + scroll_output.inner_rect.height();
+        logger.set_scrolled_to_bottom(scroll_bottom >= scroll_output.content_size.y - 1.0);
+        if !logger.scrolled_to_bottom() && unread > 0 {
+            show_jump_to_newest(logger, &mut log_ui, scroll_output.inner_rect, unread);
+        }
     }
 
     // Add input area at the bottom if enabled
     if logger.show_input_area {
-        // Move to the bottom of the available area
+        // Move to the bottom of the available area. The input area may grow past the bottom when its
+        // content does, until the log area makes room for it in the next frame.
         let input_rect = egui::Rect::from_min_size(
             egui::Pos2::new(available_rect.min.x, available_rect.min.y + log_area_height),
-            egui::Vec2::new(available_rect.width(), input_height),
+            available_rect.size(),
         );
 
         let mut input_ui = ui.new_child(
@@ -238,16 +468,58 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
             // Extract values we need before creating the mutable borrow
             let max_message_length = logger.max_message_length;
             let input_hint = logger.input_hint.clone();
+            // Unique ID for focusing with ctrl+F.
+            let input_id = egui::Id::new("egui_logger_input_field");
 
-            let input_edit = egui::TextEdit::singleline(logger.input_text_mut())
-                .char_limit(max_message_length)
-                .cursor_at_end(true)
-                .hint_text(input_hint)
-                // Unique ID for focusing with ctrl+F.
-                .id(egui::Id::new("egui_logger_input_field"))
-                .desired_width(f32::INFINITY);
+            // Completion keys need to be consumed before the TextEdit sees them, so this relies on
+            // the focus from the previous frame.
+            let completing = logger.enable_commands && logger.input_text().starts_with('/');
+            if ui.memory(|m| m.has_focus(input_id)) {
+                if completing {
+                    handle_completion_keys(logger, ui, input_id);
+                }
+                // Up/Down move between lines when there are several.
+                if logger.completion().is_none() && !logger.input_text().contains('\n') {
+                    handle_history_keys(logger, ui, input_id);
+                }
+            }
+
+            show_input_pickers(logger, ui);
 
-            let response = ui.add(input_edit);
+            let multiline = logger.multiline_input;
+            let max_rows = logger.max_input_rows();
+            let input_edit = if multiline {
+                egui::TextEdit::multiline(logger.input_text_mut())
+                    .return_key(KeyboardShortcut::new(Modifiers::SHIFT, Key::Enter))
+                    .desired_rows(1)
+            } else {
+                egui::TextEdit::singleline(logger.input_text_mut())
+            }
+            .char_limit(max_message_length)
+            .cursor_at_end(true)
+            .hint_text(input_hint)
+            .id(input_id)
+            .margin(INPUT_MARGIN)
+            // Tab is used for completion rather than moving focus while typing a command.
+            .lock_focus(completing)
+            .desired_width(f32::INFINITY);
+
+            let response = if multiline {
+                egui::ScrollArea::vertical()
+                    .max_height(input_text_edit_max_height(ui, max_rows))
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| ui.add(input_edit))
+                    .inner
+            } else {
+                ui.add(input_edit)
+            };
+            if response.changed() {
+                logger.update_completion();
+                logger.reset_history_navigation();
+            }
+            if response.has_focus() {
+                show_completion_popup(logger, ui, &response);
+            }
 
             // Check for Ctrl+F to open search
             if response.has_focus() && ui.input(|i| i.key_pressed(egui::Key::F) && i.modifiers.ctrl) {
@@ -256,15 +528,21 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
             }
 
             // Check for Enter key press to submit
-            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                if !logger.input_text().trim().is_empty() {
-                    let prefix_text: String = logger.input_text_prefix.chars().take(128).collect();
+            // If input_text is empty and Enter was pressed, focus is lost, no log, no refocus. This allows "escaping" the input field.
+            // A multi-line TextEdit keeps focus on Enter, since only Shift+Enter is its return key.
+            let submitted = if multiline {
+                response.has_focus() && ui.input(|i| i.key_pressed(Key::Enter) && !i.modifiers.shift)
+            } else {
+                response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter))
+            };
+            if submitted {
+                if logger.input_text().trim().is_empty() {
+                    response.surrender_focus();
+                } else {
                     let current_input = logger.take_input_text();
-                    let submitted_text = format!("{}{}", prefix_text, current_input);
-                    logger.log_info(logger.input_categories().to_vec(), submitted_text.as_str());
+                    logger.submit_input(&current_input);
                     response.request_focus(); // Keep focus on the input field after submit.
                 }
-                // If input_text was empty and Enter was pressed, focus is lost, no log, no refocus. This allows "escaping" the input field.
             }
 
             if logger.should_focus_input {
@@ -272,6 +550,781 @@ pub fn render_logger_ui(logger: &mut EguiLogger, ui: &mut egui::Ui) {
                 logger.should_focus_input = false;
             }
         });
+
+        // Wrapped or added lines change the height, which is only known now that it's laid out.
+        let height = input_ui.min_rect().height();
+        if logger
+            .input_area_height()
+            .is_none_or(|previous| (previous - height).abs() > 0.5)
+        {
+            logger.set_input_area_height(height);
+            ui.ctx().request_repaint();
+        }
+    }
+}
+
+/// Minimum width of a table column, so it can still be found and resized.
+const MIN_COLUMN_WIDTH: f32 = 24.0;
+
+/// Header of the table layout. Clicking a header sorts by its column, dragging its right edge
+/// resizes it, and its context menu hides or shows columns.
+fn show_table_header(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    let visible: Vec<usize> = (0..logger.table_columns.len())
+        .filter(|i| logger.table_columns[*i].visible)
+        .collect();
+    let mut sort_by = None;
+    ui.horizontal(|ui| {
+        for (n, &index) in visible.iter().enumerate() {
+            let column = logger.table_columns[index];
+            let last = n + 1 == visible.len();
+            let width = if last { ui.available_width() } else { column.width };
+            let arrow = match logger.sort_column {
+                Some(c) if c == column.column && logger.sort_descending => " ⏷",
+                Some(c) if c == column.column => " ⏶",
+                _ => "",
+            };
+            let (rect, response) = ui.allocate_exact_size(
+                egui::Vec2::new(width, ui.spacing().interact_size.y),
+                egui::Sense::click(),
+            );
+            ui.painter_at(rect).text(
+                rect.left_center(),
+                egui::Align2::LEFT_CENTER,
+                format!("{}{}", column.column.as_str(), arrow),
+                egui::TextStyle::Button.resolve(ui.style()),
+                ui.visuals().strong_text_color(),
+            );
+            if response.clicked() {
+                sort_by = Some(column.column);
+            }
+            response.context_menu(|ui| show_column_toggles(logger, ui));
+
+            if !last {
+                let handle_rect = egui::Rect::from_x_y_ranges(
+                    rect.right()..=rect.right() + ui.spacing().item_spacing.x,
+                    rect.y_range(),
+                );
+                let handle = ui.interact(
+                    handle_rect,
+                    ui.id().with(("egui_logger_column", index)),
+                    egui::Sense::drag(),
+                );
+                let active = handle.hovered() || handle.dragged();
+                if active {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+                }
+                let stroke = match active {
+                    true => ui.visuals().widgets.hovered.fg_stroke,
+                    false => ui.visuals().widgets.noninteractive.bg_stroke,
+                };
+                ui.painter()
+                    .vline(handle_rect.center().x, handle_rect.y_range(), stroke);
+                logger.table_columns[index].width = (width + handle.drag_delta().x).max(MIN_COLUMN_WIDTH);
+            }
+        }
+    });
+    if let Some(column) = sort_by {
+        logger.toggle_table_sort(column);
+    }
+    ui.separator();
+}
+
+/// A change requested from a record's context menu, or by clicking a pinned record. These are
+/// applied after the records are shown, since the logger can't be changed while its records are
+/// borrowed.
+enum RecordAction {
+    JumpTo(u64),
+    ShowFrom(DateTime<FixedOffset>),
+    ShowUntil(DateTime<FixedOffset>),
+    Bookmark(u64, String),
+    RemoveBookmark(u64),
+    Pin(u64, bool),
+    PinSelected,
+}
+
+impl RecordAction {
+    fn apply(self, logger: &mut EguiLogger) {
+        match self {
+            RecordAction::JumpTo(id) => logger.jump_to_record(id),
+            RecordAction::ShowFrom(time) => logger.show_from(time),
+            RecordAction::ShowUntil(time) => logger.show_until(time),
+            RecordAction::Bookmark(id, note) => {
+                logger.bookmark_record(id, note);
+            }
+            RecordAction::RemoveBookmark(id) => {
+                logger.remove_bookmark(id);
+            }
+            RecordAction::Pin(id, pinned) => {
+                logger.set_pinned(id, pinned);
+            }
+            RecordAction::PinSelected => logger.pin_selected(),
+        }
+    }
+}
+
+/// The context menu of a record in the log area or the pinned records.
+fn show_record_menu(
+    logger: &EguiLogger,
+    response: &egui::Response,
+    record: &LogRecord,
+    raw_text: String,
+    action: &mut Option<RecordAction>,
+) {
+    response.context_menu(|ui| {
+        if logger.show_categories {
+            ui.label(record.categories.join(","));
+        }
+        if ui.button("Copy").clicked() {
+            ui.ctx().copy_text(raw_text);
+            ui.close();
+        }
+        if logger.has_selection() && ui.button("Copy Selected").clicked() {
+            ui.ctx().copy_text(logger.selected_text());
+            ui.close();
+        }
+        ui.separator();
+        match &record.bookmark {
+            Some(note) => {
+                let mut note = note.clone();
+                ui.horizontal(|ui| {
+                    ui.label("Note:");
+                    if ui.text_edit_singleline(&mut note).changed() {
+                        *action = Some(RecordAction::Bookmark(record.id, note));
+                    }
+                });
+                if ui.button("Remove Bookmark").clicked() {
+                    *action = Some(RecordAction::RemoveBookmark(record.id));
+                    ui.close();
+                }
+            }
+            None => {
+                if ui.button("Bookmark").clicked() {
+                    *action = Some(RecordAction::Bookmark(record.id, String::new()));
+                }
+            }
+        }
+        let pin_text = if record.pinned { "Unpin" } else { "Pin" };
+        if ui.button(pin_text).clicked() {
+            *action = Some(RecordAction::Pin(record.id, !record.pinned));
+            ui.close();
+        }
+        if logger.has_selection() && ui.button("Pin Selected").clicked() {
+            *action = Some(RecordAction::PinSelected);
+            ui.close();
+        }
+        ui.separator();
+        if ui.button("Show From Here").clicked() {
+            *action = Some(RecordAction::ShowFrom(record.timestamp));
+            ui.close();
+        }
+        if ui.button("Show Until Here").clicked() {
+            *action = Some(RecordAction::ShowUntil(record.timestamp));
+            ui.close();
+        }
+    });
+}
+
+/// Marks a bookmarked record with a bar at the left of its row, and shows the bookmark's note
+/// when hovered.
+fn paint_bookmark_marker(ui: &egui::Ui, record: &LogRecord, response: &egui::Response) {
+    let Some(note) = &record.bookmark else {
+        return;
+    };
+    let rect = response.rect;
+    let bar = egui::Rect::from_x_y_ranges(rect.left()..=rect.left() + 2.0, rect.y_range());
+    ui.painter().rect_filled(bar, 0.0, ui.visuals().warn_fg_color);
+    if !note.is_empty() {
+        response.clone().on_hover_text(note);
+    }
+}
+
+/// Pinned records, shown above the log area so they stay visible while it scrolls.
+fn show_pinned_records(logger: &mut EguiLogger, ui: &mut egui::Ui, time_padding: usize) {
+    let mut action = None;
+    let pinned = logger.pinned_records();
+    if pinned.is_empty() {
+        return;
+    }
+    egui::ScrollArea::vertical()
+        .id_salt("egui_logger_pinned")
+        .max_height(ui.available_height() / 3.0)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            for record in pinned {
+                let layout_job = format_record(logger, record, time_padding, ui);
+                let raw_text = layout_job.text.clone();
+                let label = egui::Label::new(layout_job).selectable(logger.select_text);
+                let response = ui.add(label.sense(egui::Sense::click()));
+                if response.clicked() {
+                    action = Some(RecordAction::JumpTo(record.id));
+                }
+                paint_bookmark_marker(ui, record, &response);
+                show_record_menu(logger, &response, record, raw_text, &mut action);
+            }
+        });
+    ui.separator();
+    if let Some(action) = action {
+        action.apply(logger);
+    }
+}
+
+/// Lists the bookmarked records. Clicking one selects it and scrolls to it.
+fn show_bookmarks_menu(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    let mut jump_to = None;
+    let bookmarks = logger.bookmarked_records();
+    if bookmarks.is_empty() {
+        ui.weak("Right click a record to bookmark it");
+    }
+    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+        for record in bookmarks {
+            let text = match record.bookmark.as_deref() {
+                Some("") | None => &record.message,
+                Some(note) => note,
+            };
+            let label = format!("{} {}", logger.format_record_time(record).trim(), text);
+            let button = egui::Button::new(RichText::new(label).monospace()).truncate();
+            if ui.add(button).on_hover_text(&record.message).clicked() {
+                jump_to = Some(record.id);
+            }
+        }
+    });
+    if let Some(id) = jump_to {
+        logger.jump_to_record(id);
+        ui.close();
+    }
+}
+
+/// Height of the timeline strip.
+const TIMELINE_HEIGHT: f32 = 40.0;
+/// Width of each bar of the timeline strip.
+const TIMELINE_BAR_WIDTH: f32 = 4.0;
+
+/// A strip with stacked bars of the number of records of each level over time. Dragging across
+/// it sets the time range filter, and clicking it scrolls the log to that moment.
+fn show_timeline(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    let size = egui::Vec2::new(ui.available_width(), TIMELINE_HEIGHT);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+    let bins = (rect.width() / TIMELINE_BAR_WIDTH).max(1.0) as usize;
+    let Some(timeline) = logger.timeline(bins) else {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "No records",
+            egui::FontId::default(),
+            ui.visuals().weak_text_color(),
+        );
+        return;
+    };
+
+    let max_total = timeline.max_total().max(1) as f32;
+    let bar_width = rect.width() / bins as f32;
+    for bin in 0..bins {
+        let x = rect.left() + bin as f32 * bar_width;
+        let mut bottom = rect.bottom();
+        // Most severe at the bottom, so spikes of errors stand out.
+        for level in [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug] {
+            let height = timeline.count(bin, level) as f32 / max_total * rect.height();
+            if height > 0.0 {
+                let bar = egui::Rect::from_x_y_ranges(x..=x + (bar_width - 1.0).max(1.0), bottom - height..=bottom);
+                painter.rect_filled(bar, 0.0, get_level_color(level, ui));
+                bottom -= height;
+            }
+        }
+    }
+
+    let x_at = |time| rect.left() + timeline.fraction_of(time) as f32 * rect.width();
+    let fraction_at = |x: f32| ((x - rect.left()) / rect.width()) as f64;
+    let highlight = ui.visuals().selection.bg_fill.gamma_multiply(0.3);
+    if let Some(range) = logger.time_range {
+        let (start, end) = range.bounds(logger.now().fixed_offset());
+        let left = start.map_or(rect.left(), x_at);
+        let right = end.map_or(rect.right(), x_at);
+        painter.rect_filled(
+            egui::Rect::from_x_y_ranges(left..=right, rect.y_range()),
+            0.0,
+            highlight,
+        );
+    }
+
+    // The pointer's press origin is gone by the time the drag stops, so it's kept until then.
+    let drag_start_id = response.id.with("drag_start");
+    if response.drag_started()
+        && let Some(origin) = ui.input(|i| i.pointer.press_origin())
+    {
+        ui.data_mut(|d| d.insert_temp(drag_start_id, origin.x));
+    }
+    let drag_start: Option<f32> = ui.data(|d| d.get_temp(drag_start_id));
+    let pointer_x = ui
+        .input(|i| i.pointer.latest_pos())
+        .map(|pos| pos.x.clamp(rect.left(), rect.right()));
+    if let (Some(start_x), Some(end_x)) = (drag_start, pointer_x) {
+        let (left, right) = (start_x.min(end_x), start_x.max(end_x));
+        if response.dragged() {
+            painter.rect_filled(
+                egui::Rect::from_x_y_ranges(left..=right, rect.y_range()),
+                0.0,
+                highlight,
+            );
+        }
+        if response.drag_stopped() {
+            ui.data_mut(|d| d.remove::<f32>(drag_start_id));
+            logger.time_range = Some(TimeRange::Between {
+                start: Some(timeline.time_at(fraction_at(left))),
+                end: Some(timeline.time_at(fraction_at(right))),
+            });
+        }
+    }
+
+    if response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        logger.jump_to_time(timeline.time_at(fraction_at(pos.x)));
+    }
+
+    if !response.dragged()
+        && let Some(pos) = response.hover_pos()
+    {
+        let bin = (((pos.x - rect.left()) / bar_width) as usize).min(bins - 1);
+        let (start, end) = timeline.bin_range(bin);
+        let mut text = format!(
+            "{} to {}",
+            logger.format_zoned_time(start),
+            logger.format_zoned_time(end)
+        );
+        for level in [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug] {
+            text.push_str(&format!("\n{}: {}", level.as_str(), timeline.count(bin, level)));
+        }
+        response.on_hover_text_at_pointer(text);
+    }
+}
+
+/// Preset ranges of the Time Range menu, in minutes.
+const TIME_RANGE_PRESETS: [(&str, u64); 5] = [
+    ("Last Minute", 1),
+    ("Last 5 Minutes", 5),
+    ("Last 15 Minutes", 15),
+    ("Last Hour", 60),
+    ("Last Day", 24 * 60),
+];
+
+fn show_time_range_menu(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    if ui.selectable_label(logger.time_range.is_none(), "All Time").clicked() {
+        logger.time_range = None;
+    }
+    for (label, minutes) in TIME_RANGE_PRESETS {
+        let range = TimeRange::Last(Duration::from_secs(minutes * 60));
+        if ui.selectable_label(logger.time_range == Some(range), label).clicked() {
+            logger.time_range = Some(range);
+        }
+    }
+    ui.horizontal(|ui| {
+        let mut minutes = match logger.time_range {
+            Some(TimeRange::Last(duration)) => duration.as_secs() / 60,
+            _ => 30,
+        };
+        ui.label("Last");
+        if ui.add(egui::DragValue::new(&mut minutes).range(1..=10080)).changed() {
+            logger.time_range = Some(TimeRange::Last(Duration::from_secs(minutes * 60)));
+        }
+        ui.label("minutes");
+    });
+    ui.separator();
+    match logger.time_range {
+        Some(TimeRange::Between { .. }) => {
+            ui.label(logger.time_range_text().unwrap_or_default());
+        }
+        _ => {
+            ui.weak("Right click a record to show from or until it");
+        }
+    }
+}
+
+/// Checkboxes to show or hide each column of the table layout.
+fn show_column_toggles(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    for TableColumn { column, visible, .. } in &mut logger.table_columns {
+        ui.checkbox(visible, column.as_str());
+    }
+}
+
+/// A record as a row of the table layout, with a cell per visible column.
+fn show_table_row(logger: &EguiLogger, ui: &mut egui::Ui, record: &LogRecord) -> egui::Response {
+    let color = get_level_color(record.level, ui);
+    let columns: Vec<&TableColumn> = logger.table_columns.iter().filter(|c| c.visible).collect();
+    let row = ui.horizontal_top(|ui| {
+        let mut cells = Vec::new();
+        for (n, column) in columns.iter().enumerate() {
+            let last = n + 1 == columns.len();
+            let width = if last { ui.available_width() } else { column.width };
+            let text = RichText::new(logger.column_text(record, column.column))
+                .monospace()
+                .color(color);
+            let cell =
+                ui.allocate_ui_with_layout(egui::Vec2::new(width, 0.0), egui::Layout::top_down(Align::LEFT), |ui| {
+                    ui.set_width(width);
+                    // Only the last column wraps, so long category lists don't push other cells around.
+                    let label = egui::Label::new(text).selectable(logger.select_text);
+                    ui.add(if last { label.wrap() } else { label.truncate() })
+                });
+            cells.push(cell.inner);
+        }
+        cells
+    });
+    // Selectable cells take the clicks on their text, so they count as clicks on the row.
+    let response = row.response.interact(egui::Sense::click());
+    row.inner.into_iter().fold(response, |row, cell| row | cell)
+}
+
+/// Shows the detail pane for the selected record in part of `rect`, and returns the rest of it
+/// for the log area. The edge between them can be dragged to resize the pane.
+fn show_details_pane(logger: &mut EguiLogger, ui: &mut egui::Ui, rect: egui::Rect) -> egui::Rect {
+    let handle_size = ui.spacing().item_spacing.x;
+    let min_size: f32 = 100.0;
+    let (length, vertical) = match logger.details_position {
+        DetailsPosition::Right => (rect.width(), true),
+        DetailsPosition::Bottom => (rect.height(), false),
+    };
+    let size = logger
+        .details_size
+        .clamp(min_size.min(length), (length - min_size).max(min_size));
+    let (log_rect, handle_rect, details_rect) = if vertical {
+        let (log, rest) = rect.split_left_right_at_x(rect.right() - size);
+        let (handle, details) = rest.split_left_right_at_x(rest.left() + handle_size);
+        (log, handle, details)
+    } else {
+        let (log, rest) = rect.split_top_bottom_at_y(rect.bottom() - size);
+        let (handle, details) = rest.split_top_bottom_at_y(rest.top() + handle_size);
+        (log, handle, details)
+    };
+
+    let handle = ui.interact(
+        handle_rect,
+        egui::Id::new("egui_logger_details_handle"),
+        egui::Sense::drag(),
+    );
+    let stroke = match handle.hovered() || handle.dragged() {
+        true => ui.visuals().widgets.hovered.fg_stroke,
+        false => ui.visuals().widgets.noninteractive.bg_stroke,
+    };
+    if vertical {
+        ui.painter()
+            .vline(handle_rect.center().x, handle_rect.y_range(), stroke);
+        if handle.hovered() || handle.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+        }
+        logger.details_size = size - handle.drag_delta().x;
+    } else {
+        ui.painter()
+            .hline(handle_rect.x_range(), handle_rect.center().y, stroke);
+        if handle.hovered() || handle.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeVertical);
+        }
+        logger.details_size = size - handle.drag_delta().y;
+    }
+
+    let mut details_ui = ui.new_child(
+        UiBuilder::new()
+            .max_rect(details_rect)
+            .layout(egui::Layout::top_down(Align::LEFT)),
+    );
+    egui::ScrollArea::vertical()
+        .id_salt("egui_logger_details")
+        .auto_shrink([false, false])
+        .show(&mut details_ui, |ui| {
+            let Some(record) = logger.detail_record() else {
+                ui.weak("Select a record to see its details.");
+                return;
+            };
+            egui::Grid::new("egui_logger_details_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, value) in logger.record_details(record) {
+                        ui.strong(name);
+                        ui.add(egui::Label::new(&value).wrap());
+                        if ui.small_button("Copy").clicked() {
+                            ui.ctx().copy_text(value);
+                        }
+                        ui.end_row();
+                    }
+                });
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.strong("Message");
+                if ui.small_button("Copy").clicked() {
+                    ui.ctx().copy_text(record.message.clone());
+                }
+            });
+            ui.add(egui::Label::new(RichText::new(&record.message).monospace()).wrap());
+        });
+
+    log_rect
+}
+
+/// Handles Up/Down (with Shift to extend), Escape and copying for the selected records.
+fn handle_selection_keys(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    let consume = |modifiers, key| ui.input_mut(|i| i.consume_key(modifiers, key));
+    if consume(Modifiers::NONE, Key::ArrowUp) {
+        logger.move_selection(false, false);
+    } else if consume(Modifiers::NONE, Key::ArrowDown) {
+        logger.move_selection(true, false);
+    } else if consume(Modifiers::SHIFT, Key::ArrowUp) {
+        logger.move_selection(false, true);
+    } else if consume(Modifiers::SHIFT, Key::ArrowDown) {
+        logger.move_selection(true, true);
+    } else if consume(Modifiers::NONE, Key::Escape) {
+        logger.clear_selection();
+    }
+    // Ctrl+C arrives as a copy event rather than a key press. Selected text is copied by egui instead.
+    if !logger.select_text && ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy))) {
+        ui.ctx().copy_text(logger.selected_text());
+    }
+}
+
+/// A line above the first record that arrived while the user was reading older records.
+fn show_unread_divider(ui: &mut egui::Ui) {
+    let color = ui.visuals().warn_fg_color;
+    let text = ui
+        .painter()
+        .layout_no_wrap("New".to_string(), egui::TextStyle::Small.resolve(ui.style()), color);
+    let (rect, _) = ui.allocate_exact_size(
+        egui::Vec2::new(ui.available_width(), text.size().y),
+        egui::Sense::hover(),
+    );
+    let text_pos = egui::Pos2::new(rect.right() - text.size().x, rect.top());
+    ui.painter().hline(
+        rect.left()..=text_pos.x - ui.spacing().item_spacing.x,
+        rect.center().y,
+        egui::Stroke::new(1.0, color),
+    );
+    ui.painter().galley(text_pos, text, color);
+}
+
+/// A button floating over the bottom of the log area, to scroll down to the unread records.
+fn show_jump_to_newest(logger: &mut EguiLogger, ui: &mut egui::Ui, log_rect: egui::Rect, unread: usize) {
+    let text = match unread {
+        1 => "↓ 1 new message".to_string(),
+        n => format!("↓ {n} new messages"),
+    };
+    let size = egui::Vec2::new(160.0, ui.spacing().interact_size.y);
+    let margin = ui.spacing().item_spacing.y * 2.0;
+    let rect = egui::Rect::from_center_size(
+        egui::Pos2::new(log_rect.center().x, log_rect.bottom() - margin - size.y / 2.0),
+        size,
+    );
+    if ui.put(rect, egui::Button::new(text)).clicked() {
+        logger.scroll_to_newest();
+    }
+}
+
+/// Tabs to switch between filtered views of the log, with a count of unread records on each.
+/// Right clicking a tab edits it.
+fn show_tab_bar(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    let mut selected = None;
+    let mut removed = None;
+    ui.horizontal_wrapped(|ui| {
+        for index in 0..logger.tabs().len() {
+            let tab = &logger.tabs()[index];
+            let mut label = RichText::new(&tab.name);
+            if tab.unread() > 0 {
+                label = RichText::new(format!("{} ({})", tab.name, tab.unread())).strong();
+            }
+            let response = ui.selectable_label(logger.active_tab() == Some(index), label);
+            if response.clicked() {
+                selected = Some(index);
+            }
+            response.context_menu(|ui| {
+                if edit_tab(logger, ui, index) {
+                    removed = Some(index);
+                    ui.close();
+                }
+            });
+        }
+        if ui
+            .button("+")
+            .on_hover_text("New tab with the current filters")
+            .clicked()
+        {
+            logger.add_tab_from_filters();
+        }
+    });
+    if let Some(index) = selected {
+        logger.select_tab(index);
+    }
+    if let Some(index) = removed {
+        logger.remove_tab(index);
+    }
+}
+
+/// Context menu contents for editing a tab. Returns whether the tab should be closed.
+fn edit_tab(logger: &mut EguiLogger, ui: &mut egui::Ui, index: usize) -> bool {
+    let mut categories: Vec<String> = logger.category_counts().keys().cloned().collect();
+    let channels: Vec<String> = logger.input_channels().iter().map(|c| c.name.clone()).collect();
+    let Some(tab) = logger.tab_mut(index) else {
+        return false;
+    };
+    categories.extend(tab.categories.iter().cloned());
+    categories.sort();
+    categories.dedup();
+
+    ui.horizontal(|ui| {
+        ui.label("Name: ");
+        ui.text_edit_singleline(&mut tab.name);
+    });
+    ui.menu_button("Only Categories", |ui| {
+        ui.label(RichText::new("None selected shows all").weak());
+        for category in categories {
+            let included = tab.categories.contains(&category);
+            if ui.selectable_label(included, &category).clicked() {
+                if included {
+                    tab.categories.retain(|c| c != &category);
+                } else {
+                    tab.categories.push(category);
+                }
+            }
+        }
+    });
+    if !channels.is_empty() {
+        ui.menu_button("Input Channel", |ui| {
+            ui.radio_value(&mut tab.input_channel, None, "Unchanged");
+            for channel in channels {
+                ui.radio_value(&mut tab.input_channel, Some(channel.clone()), channel);
+            }
+        });
+    }
+    ui.separator();
+    ui.button("Close Tab").clicked()
+}
+
+/// Dropdowns next to the input area to pick the input channel and level, if the app provided choices.
+fn show_input_pickers(logger: &mut EguiLogger, ui: &mut egui::Ui) {
+    if !logger.input_channels().is_empty() {
+        let selected = logger.selected_input_channel().unwrap_or_default().to_string();
+        let mut picked = None;
+        egui::ComboBox::from_id_salt("egui_logger_input_channel")
+            .selected_text(&selected)
+            .width(0.0)
+            .show_ui(ui, |ui| {
+                for channel in logger.input_channels() {
+                    if ui.selectable_label(channel.name == selected, &channel.name).clicked() {
+                        picked = Some(channel.name.clone());
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Channel");
+        if let Some(name) = picked {
+            logger.select_input_channel(&name);
+            logger.should_focus_input = true;
+        }
+    }
+    if !logger.input_levels.is_empty() {
+        let levels = logger.input_levels.clone();
+        egui::ComboBox::from_id_salt("egui_logger_input_level")
+            .selected_text(logger.input_level.as_str())
+            .width(0.0)
+            .show_ui(ui, |ui| {
+                for level in levels {
+                    if ui
+                        .selectable_value(&mut logger.input_level, level, level.as_str())
+                        .clicked()
+                    {
+                        logger.should_focus_input = true;
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Log level");
+    }
+}
+
+/// Margin inside the input TextEdit. Set explicitly, since its height in multi-line mode is
+/// computed from it.
+const INPUT_MARGIN: egui::Margin = egui::Margin::symmetric(4, 2);
+
+/// Height of the input TextEdit when showing `rows` rows of text, including wrapped rows.
+fn input_text_edit_max_height(ui: &egui::Ui, rows: usize) -> f32 {
+    let font_id = FontSelection::Default.resolve(ui.style());
+    let row_height = ui.fonts(|f| f.row_height(&font_id));
+    rows as f32 * row_height + INPUT_MARGIN.sum().y
+}
+
+/// Handles Tab, Shift+Tab, arrows, Enter and Escape for the completion suggestions of the input area.
+fn handle_completion_keys(logger: &mut EguiLogger, ui: &mut egui::Ui, input_id: egui::Id) {
+    let consume = |modifiers, key| ui.input_mut(|i| i.consume_key(modifiers, key));
+    let popup_open = logger.completion().is_some();
+    let mut changed = false;
+    if consume(Modifiers::SHIFT, Key::Tab) || (popup_open && consume(Modifiers::NONE, Key::ArrowUp)) {
+        changed = logger.cycle_completion(false);
+    } else if consume(Modifiers::NONE, Key::Tab) || (popup_open && consume(Modifiers::NONE, Key::ArrowDown)) {
+        changed = logger.cycle_completion(true);
+    } else if popup_open && consume(Modifiers::NONE, Key::Escape) {
+        logger.close_completion();
+    } else if logger.completion().is_some_and(|c| c.selected.is_some()) && consume(Modifiers::NONE, Key::Enter) {
+        // Accept the selected suggestion rather than submitting the input.
+        logger.close_completion();
+    }
+    if changed {
+        move_cursor_to_end(ui.ctx(), input_id, logger.input_text());
+    }
+}
+
+/// Handles Up/Down and Ctrl+R to recall previous inputs in the input area.
+fn handle_history_keys(logger: &mut EguiLogger, ui: &mut egui::Ui, input_id: egui::Id) {
+    let consume = |modifiers, key| ui.input_mut(|i| i.consume_key(modifiers, key));
+    let prefix_search = logger.history_prefix_search;
+    let changed = if consume(Modifiers::NONE, Key::ArrowUp) {
+        logger.history_previous(prefix_search)
+    } else if consume(Modifiers::COMMAND, Key::R) {
+        logger.history_previous(true)
+    } else if consume(Modifiers::NONE, Key::ArrowDown) {
+        logger.history_next(prefix_search)
+    } else {
+        false
+    };
+    if changed {
+        logger.close_completion();
+        move_cursor_to_end(ui.ctx(), input_id, logger.input_text());
+    }
+}
+
+fn move_cursor_to_end(ctx: &egui::Context, id: egui::Id, text: &str) {
+    if let Some(mut state) = egui::TextEdit::load_state(ctx, id) {
+        let end = CCursor::new(text.chars().count());
+        state.cursor.set_char_range(Some(CCursorRange::one(end)));
+        state.store(ctx, id);
+    }
+}
+
+/// Shows the completion suggestions in a popup below the input area.
+fn show_completion_popup(logger: &mut EguiLogger, ui: &egui::Ui, response: &egui::Response) {
+    let Some(completion) = logger.completion() else {
+        return;
+    };
+    let mut clicked = None;
+    egui::Popup::from_response(response)
+        .id(egui::Id::new("egui_logger_completion_popup"))
+        .open(true)
+        .align(RectAlign::BOTTOM_START)
+        .align_alternatives(&[RectAlign::TOP_START])
+        .close_behavior(PopupCloseBehavior::IgnoreClicks)
+        .show(|ui| {
+            for (i, candidate) in completion.candidates.iter().enumerate() {
+                let label = ui.selectable_label(completion.selected == Some(i), candidate);
+                if completion.selected == Some(i) {
+                    label.scroll_to_me(None);
+                }
+                if label.clicked() {
+                    clicked = Some(i);
+                }
+            }
+        });
+    if let Some(i) = clicked {
+        logger.select_completion(i);
+        logger.close_completion();
+        move_cursor_to_end(ui.ctx(), response.id, logger.input_text());
+        response.request_focus();
     }
 }
 
@@ -303,7 +1356,7 @@ fn format_record(logger: &EguiLogger, record: &LogRecord, time_padding: usize, u
 
     let date_str = RichText::new(format!(
         "{: >width$}",
-        logger.format_time(record.timestamp),
+        logger.format_record_time(record),
         width = time_padding
     ))
     .monospace()
@@ -318,5 +1371,14 @@ fn format_record(logger: &EguiLogger, record: &LogRecord, time_padding: usize, u
     let message = RichText::new(&record.message).monospace().color(level_color);
     message.append_to(&mut layout_job, &style, FontSelection::Default, Align::LEFT);
 
+    let repeats = logger.format_repeats(record);
+    if !repeats.is_empty() {
+        RichText::new(repeats)
+            .monospace()
+            .color(level_color)
+            .strong()
+            .append_to(&mut layout_job, &style, FontSelection::Default, Align::LEFT);
+    }
+
     layout_job
 }